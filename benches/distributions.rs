@@ -17,7 +17,7 @@ extern crate test;
 const RAND_BENCH_N: u64 = 1000;
 
 use rand::distributions::{Alphanumeric, Open01, OpenClosed01, Standard, Uniform};
-use rand::distributions::uniform::{UniformInt, UniformSampler};
+use rand::distributions::uniform::{IntSampleStrategy, UniformInt, UniformSampler};
 use std::mem::size_of;
 use std::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8};
 use std::time::Duration;
@@ -141,6 +141,9 @@ distr_int!(distr_uniform_usize32, usize, Uniform::new(0usize, 0x548c0f43));
 #[cfg(target_pointer_width = "64")]
 distr_int!(distr_uniform_usize64, usize, Uniform::new(0usize, 0x3a42714f2bf927a8));
 distr_int!(distr_uniform_isize, isize, Uniform::new(-1060478432isize, 1858574057));
+// Power-of-2 ranges skip the widening multiply
+distr_int!(distr_uniform_u64_pow2, u64, Uniform::new(0u64, 256));
+distr_int!(distr_uniform_u64_npow2, u64, Uniform::new(0u64, 255));
 
 distr_float!(distr_uniform_f32, f32, Uniform::new(2.26f32, 2.319));
 distr_float!(distr_uniform_f64, f64, Uniform::new(2.26f64, 2.319));
@@ -253,6 +256,42 @@ gen_range_float!(gen_range_f32, f32, -20000.0f32, 100000.0);
 gen_range_float!(gen_range_f64, f64, 123.456f64, 7890.12);
 
 
+// sample from a range using each of the available integer strategies
+macro_rules! gen_range_strategy {
+    ($fnn:ident, $ty:ident, $strategy:ident, $low:expr, $high:expr) => {
+        #[bench]
+        fn $fnn(b: &mut Bencher) {
+            let mut rng = Pcg64Mcg::from_entropy();
+            let low = black_box($low);
+            let high = black_box($high);
+
+            b.iter(|| {
+                let mut accum: $ty = 0;
+                for _ in 0..RAND_BENCH_N {
+                    let x = UniformInt::<$ty>::sample_single_inclusive_with(
+                        low, high, IntSampleStrategy::$strategy, &mut rng);
+                    accum = accum.wrapping_add(x);
+                }
+                accum
+            });
+            b.bytes = size_of::<$ty>() as u64 * RAND_BENCH_N;
+        }
+    };
+}
+
+gen_range_strategy!(gen_range_lemire_u8_small, u8, Lemire, 0u8, 5);
+gen_range_strategy!(gen_range_rejection_u8_small, u8, Rejection, 0u8, 5);
+gen_range_strategy!(gen_range_modulo_u8_small, u8, BiasedModulo, 0u8, 5);
+gen_range_strategy!(gen_range_lemire_u32_small, u32, Lemire, 0u32, 5);
+gen_range_strategy!(gen_range_rejection_u32_small, u32, Rejection, 0u32, 5);
+gen_range_strategy!(gen_range_modulo_u32_small, u32, BiasedModulo, 0u32, 5);
+gen_range_strategy!(gen_range_lemire_u32_half, u32, Lemire, 0u32, 0x8000_0000);
+gen_range_strategy!(gen_range_rejection_u32_half, u32, Rejection, 0u32, 0x8000_0000);
+gen_range_strategy!(gen_range_modulo_u32_half, u32, BiasedModulo, 0u32, 0x8000_0000);
+gen_range_strategy!(gen_range_lemire_u64_large, u64, Lemire, 3u64, 123_456_789_123_456);
+gen_range_strategy!(gen_range_rejection_u64_large, u64, Rejection, 3u64, 123_456_789_123_456);
+gen_range_strategy!(gen_range_modulo_u64_large, u64, BiasedModulo, 3u64, 123_456_789_123_456);
+
 // In src/distributions/uniform.rs, we say:
 // Implementation of [`uniform_single`] is optional, and is only useful when
 // the implementation can be faster than `Self::new(low, high).sample(rng)`.
@@ -181,3 +181,27 @@ fn gen_1kb_u64_fill(b: &mut Bencher) {
     });
     b.bytes = 1024;
 }
+
+#[bench]
+fn gen_1kb_f64_gen_loop(b: &mut Bencher) {
+    let mut rng = Pcg64Mcg::from_rng(&mut thread_rng()).unwrap();
+    let mut buf = [0f64; 128];
+    b.iter(|| {
+        for x in buf.iter_mut() {
+            *x = rng.gen();
+        }
+        buf
+    });
+    b.bytes = 1024;
+}
+
+#[bench]
+fn gen_1kb_f64_fill_uniform(b: &mut Bencher) {
+    let mut rng = Pcg64Mcg::from_rng(&mut thread_rng()).unwrap();
+    let mut buf = [0f64; 128];
+    b.iter(|| {
+        rng.fill_uniform_f64(&mut buf);
+        buf
+    });
+    b.bytes = 1024;
+}
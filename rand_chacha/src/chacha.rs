@@ -67,6 +67,38 @@ impl<T> fmt::Debug for Array64<T> {
     }
 }
 
+/// An iterator over the raw keystream bytes of a ChaCha RNG.
+///
+/// This struct is created by the `keystream_iter` method on the ChaCha RNG
+/// types. Bytes are yielded in the same order as they would be written by
+/// `fill_bytes`, i.e. each 32-bit word of the keystream in little-endian order.
+#[derive(Debug)]
+pub struct KeystreamIter<'a, R: ?Sized> {
+    rng: &'a mut R,
+    word: [u8; 4],
+    index: usize,
+}
+
+impl<'a, R: RngCore + CryptoRng + ?Sized> Iterator for KeystreamIter<'a, R> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.index >= self.word.len() {
+            self.word = self.rng.next_u32().to_le_bytes();
+            self.index = 0;
+        }
+        let byte = self.word[self.index];
+        self.index += 1;
+        Some(byte)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (::core::usize::MAX, None)
+    }
+}
+
 macro_rules! chacha_impl {
     ($ChaChaXCore:ident, $ChaChaXRng:ident, $rounds:expr, $doc:expr, $abst:ident) => {
         #[doc=$doc]
@@ -262,6 +294,22 @@ macro_rules! chacha_impl {
                     .state
                     .get_seed()
             }
+
+            /// Get an iterator over the raw keystream bytes.
+            ///
+            /// The iterator continues from the current position of the
+            /// generator and advances it as bytes are consumed; the bytes are
+            /// the same as those written by `fill_bytes`. Since the keystream
+            /// is deterministic for a given seed, stream and word position,
+            /// this may be used to reproduce test vectors for the cipher.
+            #[inline]
+            pub fn keystream_iter(&mut self) -> KeystreamIter<'_, Self> {
+                KeystreamIter {
+                    rng: self,
+                    word: [0; 4],
+                    index: 4,
+                }
+            }
         }
 
         impl CryptoRng for $ChaChaXRng {}
@@ -557,6 +605,42 @@ mod test {
         assert_eq!(results, expected);
     }
 
+    #[test]
+    fn test_chacha_keystream_iter() {
+        // Test vector from RFC 8439 section 2.3.2: key 00:01:02:..:1f,
+        // nonce 00:00:00:09:00:00:00:4a:00:00:00:00 and block counter 1.
+        let mut seed = [0u8; 32];
+        for (i, b) in seed.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let mut rng = ChaChaRng::from_seed(seed);
+        // The first 32 bits of the nonce form the upper half of our counter.
+        rng.set_word_pos(((0x09000000u128 << 32) | 1) * 16);
+        rng.set_stream(0x4a000000);
+
+        let mut results = [0u8; 64];
+        for (r, b) in results.iter_mut().zip(rng.keystream_iter()) {
+            *r = b;
+        }
+        let expected = [
+            0x10, 0xf1, 0xe7, 0xe4, 0xd1, 0x3b, 0x59, 0x15, 0x50, 0x0f, 0xdd, 0x1f, 0xa3, 0x20,
+            0x71, 0xc4, 0xc7, 0xd1, 0xf4, 0xc7, 0x33, 0xc0, 0x68, 0x03, 0x04, 0x22, 0xaa, 0x9a,
+            0xc3, 0xd4, 0x6c, 0x4e, 0xd2, 0x82, 0x64, 0x46, 0x07, 0x9f, 0xaa, 0x09, 0x14, 0xc2,
+            0xd7, 0x05, 0xd9, 0x8b, 0x02, 0xa2, 0xb5, 0x12, 0x9c, 0xd1, 0xde, 0x16, 0x4e, 0xb9,
+            0xcb, 0xd0, 0x83, 0xe8, 0xa2, 0x50, 0x3c, 0x4e,
+        ];
+        assert_eq!(&results[..], &expected[..]);
+
+        // The iterator yields the same bytes as `fill_bytes`
+        let mut rng = ChaChaRng::from_seed(seed);
+        let mut clone = rng.clone();
+        let mut bytes = [0u8; 37];
+        clone.fill_bytes(&mut bytes);
+        for (a, b) in rng.keystream_iter().zip(bytes.iter()) {
+            assert_eq!(a, *b);
+        }
+    }
+
     #[test]
     fn test_chacha_nonce() {
         // Test vector 5 from
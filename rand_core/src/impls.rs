@@ -146,6 +146,35 @@ pub fn next_u64_via_fill<R: RngCore + ?Sized>(rng: &mut R) -> u64 {
     u64::from_le_bytes(buf)
 }
 
+/// Mix the bits of a `u64`, using the finalizer of the SplitMix64 generator.
+///
+/// This is a bijection with good avalanche properties: flipping any input bit
+/// flips each output bit with probability close to one half. It is not
+/// cryptographically secure, but is useful to derive well-distributed seeds
+/// or hashes from structured inputs such as counters or coordinates.
+///
+/// Note that `mix_u64(0) == 0`; SplitMix64 itself mixes its state after
+/// adding the constant `0x9e3779b97f4a7c15` on each step.
+#[inline]
+pub fn mix_u64(x: u64) -> u64 {
+    let mut z = x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Mix the bits of a `u32`, using the 32-bit finalizer of MurmurHash3.
+///
+/// This is the 32-bit counterpart of [`mix_u64`], with the same properties.
+/// Note that `mix_u32(0) == 0`.
+#[inline]
+pub fn mix_u32(x: u32) -> u32 {
+    let mut z = x;
+    z = (z ^ (z >> 16)).wrapping_mul(0x85ebca6b);
+    z = (z ^ (z >> 13)).wrapping_mul(0xc2b2ae35);
+    z ^ (z >> 16)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -181,4 +210,23 @@ mod test {
         assert_eq!(fill_via_u64_chunks(&src, &mut dst), (1, 5));
         assert_eq!(dst, [1, 0, 0, 0, 0]);
     }
+
+    #[test]
+    fn test_mix_u64() {
+        // Outputs of the reference SplitMix64 generator seeded with 0
+        const PHI: u64 = 0x9e3779b97f4a7c15;
+        assert_eq!(mix_u64(PHI), 0xe220a8397b1dcdaf);
+        assert_eq!(mix_u64(PHI.wrapping_mul(2)), 0x6e789e6aa1b965f4);
+        assert_eq!(mix_u64(PHI.wrapping_mul(3)), 0x06c45d188009454f);
+        assert_eq!(mix_u64(0), 0);
+    }
+
+    #[test]
+    fn test_mix_u32() {
+        // Reference MurmurHash3 `fmix32`
+        assert_eq!(mix_u32(1), 0x514e28b7);
+        assert_eq!(mix_u32(2), 0x30f4c306);
+        assert_eq!(mix_u32(0xdeadbeef), 0x0de5c6a9);
+        assert_eq!(mix_u32(0), 0);
+    }
 }
@@ -70,6 +70,125 @@ impl RngCore for OsRng {
     }
 }
 
+#[cfg(feature = "health_test")]
+mod health {
+    use core::num::NonZeroU32;
+
+    /// Number of bytes drawn by `OsRng::health_test`.
+    pub(super) const SAMPLE_LEN: usize = 1024;
+
+    // We assume the source provides full entropy, i.e. 8 bits per byte, and
+    // use a false positive probability of alpha = 2^-30 per test.
+    /// Repetition count test cutoff: `1 + ceil(30 / 8)`.
+    const REPETITION_CUTOFF: usize = 5;
+    /// Adaptive proportion test window size for non-binary samples.
+    const ADAPTIVE_WINDOW: usize = 512;
+    /// Adaptive proportion test cutoff: `1 + CRITBINOM(512, 2^-8, 1 - 2^-30)`.
+    const ADAPTIVE_CUTOFF: usize = 16;
+
+    /// Error code reported when a health test fails.
+    pub(super) const FAILURE_CODE: u32 = crate::Error::CUSTOM_START - 1;
+
+    pub(super) fn failure() -> crate::Error {
+        NonZeroU32::new(FAILURE_CODE).unwrap().into()
+    }
+
+    /// The repetition count test (NIST SP 800-90B, section 4.4.1).
+    ///
+    /// Returns `false` if any byte repeats `REPETITION_CUTOFF` or more times
+    /// in a row.
+    pub(super) fn repetition_count(sample: &[u8]) -> bool {
+        let mut prev = match sample.first() {
+            Some(&b) => b,
+            None => return true,
+        };
+        let mut count = 1;
+        for &b in &sample[1..] {
+            if b == prev {
+                count += 1;
+                if count >= REPETITION_CUTOFF {
+                    return false;
+                }
+            } else {
+                prev = b;
+                count = 1;
+            }
+        }
+        true
+    }
+
+    /// The adaptive proportion test (NIST SP 800-90B, section 4.4.2).
+    ///
+    /// Returns `false` if, in any full window, the first byte of the window
+    /// occurs `ADAPTIVE_CUTOFF` or more times.
+    pub(super) fn adaptive_proportion(sample: &[u8]) -> bool {
+        sample.chunks_exact(ADAPTIVE_WINDOW).all(|window| {
+            let first = window[0];
+            window.iter().filter(|&&b| b == first).count() < ADAPTIVE_CUTOFF
+        })
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_health_constant() {
+            let sample = [0x5au8; SAMPLE_LEN];
+            assert!(!repetition_count(&sample));
+            assert!(!adaptive_proportion(&sample));
+        }
+
+        #[test]
+        fn test_health_biased() {
+            // No long runs, but one value is far too frequent
+            let mut sample = [0u8; SAMPLE_LEN];
+            for (i, b) in sample.iter_mut().enumerate() {
+                *b = if i % 16 == 0 { 0xff } else { i as u8 ^ (i >> 8) as u8 };
+            }
+            assert!(repetition_count(&sample));
+            assert!(!adaptive_proportion(&sample));
+        }
+
+        #[test]
+        fn test_health_counter() {
+            let mut sample = [0u8; SAMPLE_LEN];
+            for (i, b) in sample.iter_mut().enumerate() {
+                *b = i as u8;
+            }
+            assert!(repetition_count(&sample));
+            assert!(adaptive_proportion(&sample));
+        }
+    }
+}
+
+#[cfg(feature = "health_test")]
+impl OsRng {
+    /// Run health tests on a fresh sample from the operating system.
+    ///
+    /// This draws a sample of 1024 bytes and applies the repetition count
+    /// and adaptive proportion tests of NIST SP 800-90B (section 4.4),
+    /// assuming full entropy and a false positive probability of `2^-30` per
+    /// test. An error is returned if the source appears to be stuck or
+    /// biased, or if the sample could not be drawn.
+    ///
+    /// Passing these tests does not guarantee the quality of the source;
+    /// they only detect catastrophic failures.
+    ///
+    /// A health test failure is reported with the error code
+    /// `Error::CUSTOM_START - 1`.
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "health_test")))]
+    pub fn health_test(&mut self) -> Result<(), Error> {
+        let mut sample = [0u8; health::SAMPLE_LEN];
+        self.try_fill_bytes(&mut sample)?;
+        if health::repetition_count(&sample) && health::adaptive_proportion(&sample) {
+            Ok(())
+        } else {
+            Err(health::failure())
+        }
+    }
+}
+
 #[test]
 fn test_os_rng() {
     let x = OsRng.next_u64();
@@ -83,3 +202,12 @@ fn test_construction() {
     let mut rng = OsRng::default();
     assert!(rng.next_u64() != 0);
 }
+
+#[test]
+#[cfg(feature = "health_test")]
+fn test_health_test() {
+    OsRng.health_test().unwrap();
+
+    let err = health::failure();
+    assert_eq!(err.code().map(|c| c.get()), Some(health::FAILURE_CODE));
+}
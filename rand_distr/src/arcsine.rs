@@ -0,0 +1,92 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The arcsine distribution.
+
+use num_traits::{Float, FloatConst};
+use crate::{Distribution, Standard};
+use rand::Rng;
+
+/// The standard arcsine distribution on `[0, 1]`.
+///
+/// This is the [`Beta`] distribution with both shape parameters equal to
+/// `1/2`. It has density function
+/// `f(x) = 1 / (pi * sqrt(x * (1 - x)))`,
+/// which is U-shaped with most mass near the end-points 0 and 1. It arises
+/// for example as the distribution of the time at which a Brownian motion on
+/// `[0, 1]` attains its maximum.
+///
+/// Samples are generated via the inverse CDF, `sin^2(pi/2 * u)` with `u`
+/// sampled from [`Standard`].
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Arcsine, Distribution};
+///
+/// let v: f64 = Arcsine.sample(&mut rand::thread_rng());
+/// println!("{} is from the arcsine distribution", v);
+/// ```
+///
+/// [`Beta`]: crate::Beta
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Arcsine;
+
+impl<F> Distribution<F> for Arcsine
+where F: Float + FloatConst, Standard: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(Standard);
+        let s = (F::FRAC_PI_2() * u).sin();
+        s * s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_arcsine_shape() {
+        const N: usize = 100_000;
+        let mut rng = crate::test::rng(1681);
+        let mut bins = [0usize; 10];
+        let mut sum = 0.0;
+        for _ in 0..N {
+            let x: f64 = Arcsine.sample(&mut rng);
+            assert!((0.0..=1.0).contains(&x));
+            sum += x;
+            bins[((x * 10.0) as usize).min(9)] += 1;
+        }
+        assert_almost_eq!(sum / N as f64, 0.5, 0.01);
+
+        // The density decreases towards the centre and is symmetric
+        for i in 0..4 {
+            assert!(bins[i] > bins[i + 1]);
+            assert!(bins[9 - i] > bins[8 - i]);
+        }
+        // P(X < 0.1) = 2/pi * asin(sqrt(0.1)) ~= 0.205
+        assert_almost_eq!(bins[0] as f64 / N as f64, 0.2048, 0.01);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(214);
+        let expected = [
+            0.9955643892783552,
+            0.0004397394513780025,
+            0.7139369247695703,
+            0.019900527980538256,
+        ];
+        for &v in expected.iter() {
+            let x: f64 = Arcsine.sample(&mut rng);
+            assert_almost_eq!(x, v, 1e-14);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Randomized exponential backoff delays.
+
+use rand::Rng;
+use core::fmt;
+use core::time::Duration;
+
+/// An iterator over randomized, exponentially growing retry delays.
+///
+/// For attempt `k` (counting from 0), the delay ceiling is
+/// `min(cap, base * multiplier^k)` and the delay is sampled uniformly from
+/// `[ceiling / 2, ceiling]` ("equal jitter"). The expected delay therefore
+/// never decreases from one attempt to the next, while the jitter spreads out
+/// retries from many clients. No delay exceeds `cap`. After `max_attempts`
+/// delays, the iterator yields `None`.
+///
+/// Delays have nanosecond resolution and ceilings are limited to about 584
+/// years (`u64::MAX` nanoseconds); longer durations are clamped.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use rand_distr::Backoff;
+///
+/// let backoff = Backoff::new(
+///     rand::thread_rng(),
+///     Duration::from_millis(100),
+///     2.0,
+///     Duration::from_secs(10),
+///     5,
+/// )
+/// .unwrap();
+/// for delay in backoff {
+///     // try the operation, and on failure sleep for `delay`
+///     assert!(delay <= Duration::from_secs(10));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Backoff<R> {
+    rng: R,
+    // Ceilings in nanoseconds
+    ceiling: f64,
+    cap: f64,
+    multiplier: f64,
+    attempts_left: u32,
+}
+
+/// Error type returned from `Backoff::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `multiplier < 1` or `nan`.
+    MultiplierTooSmall,
+    /// `cap < base`.
+    CapTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::MultiplierTooSmall => "multiplier < 1 or is NaN in backoff",
+            Error::CapTooSmall => "cap < base in backoff",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+// The largest number of nanoseconds which fits in `u64` and is exactly
+// representable as `f64`, such that ceilings can be cast back to `u64`
+const MAX_NANOS: u64 = ::core::u64::MAX << 11;
+
+fn clamped_nanos(duration: Duration) -> f64 {
+    core::cmp::min(duration.as_nanos(), u128::from(MAX_NANOS)) as u64 as f64
+}
+
+impl<R: Rng> Backoff<R> {
+    /// Construct a new `Backoff`, using `rng` to sample delays.
+    ///
+    /// The first delay ceiling is `base`, and each following ceiling is
+    /// `multiplier` times the previous one, up to `cap`. At most
+    /// `max_attempts` delays are yielded.
+    pub fn new(
+        rng: R, base: Duration, multiplier: f64, cap: Duration, max_attempts: u32,
+    ) -> Result<Backoff<R>, Error> {
+        if !(multiplier >= 1.0) {
+            return Err(Error::MultiplierTooSmall);
+        }
+        if cap < base {
+            return Err(Error::CapTooSmall);
+        }
+        Ok(Backoff {
+            rng,
+            ceiling: clamped_nanos(base),
+            cap: clamped_nanos(cap),
+            multiplier,
+            attempts_left: max_attempts,
+        })
+    }
+}
+
+impl<R: Rng> Iterator for Backoff<R> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        if self.attempts_left == 0 {
+            return None;
+        }
+        self.attempts_left -= 1;
+
+        let ceiling = self.ceiling.min(self.cap) as u64;
+        let delay = ceiling - self.rng.gen_range(0..=ceiling / 2);
+        self.ceiling = (self.ceiling * self.multiplier).min(self.cap);
+        Some(Duration::from_nanos(delay))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.attempts_left as usize;
+        (n, Some(n))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backoff_invalid() {
+        let new = |multiplier, cap| {
+            Backoff::new(crate::test::rng(0), Duration::from_secs(1), multiplier, cap, 3)
+        };
+        assert_eq!(new(0.5, Duration::from_secs(2)).err(), Some(Error::MultiplierTooSmall));
+        assert_eq!(
+            new(core::f64::NAN, Duration::from_secs(2)).err(),
+            Some(Error::MultiplierTooSmall)
+        );
+        assert_eq!(new(2.0, Duration::from_millis(999)).err(), Some(Error::CapTooSmall));
+        assert!(new(1.0, Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_backoff() {
+        const N: usize = 1000;
+        const ATTEMPTS: usize = 10;
+        let base = Duration::from_millis(10);
+        let cap = Duration::from_secs(1);
+        let mut rng = crate::test::rng(1725);
+        let mut means = [0.0; ATTEMPTS];
+        for _ in 0..N {
+            let backoff = Backoff::new(&mut rng, base, 2.0, cap, ATTEMPTS as u32).unwrap();
+            let mut count = 0;
+            for (k, delay) in backoff.enumerate() {
+                count += 1;
+                let ceiling = (base * (1 << k)).min(cap);
+                assert!(ceiling / 2 <= delay && delay <= ceiling);
+                means[k] += delay.as_nanos() as f64 / N as f64;
+            }
+            assert_eq!(count, ATTEMPTS);
+        }
+        // Non-decreasing in expectation; the mean of the first attempt is
+        // about 7.5 ms and doubles until reaching the cap
+        assert!(means.windows(2).all(|w| w[0] <= w[1] * 1.02));
+        assert_almost_eq!(means[0] / 1e6, 7.5, 0.1);
+        assert_almost_eq!(means[ATTEMPTS - 1] / 1e6, 750.0, 10.0);
+    }
+
+    #[test]
+    fn test_backoff_stops() {
+        let mut backoff = Backoff::new(
+            crate::test::rng(1726),
+            Duration::from_secs(1),
+            1.5,
+            Duration::from_secs(2),
+            2,
+        )
+        .unwrap();
+        assert_eq!(backoff.size_hint(), (2, Some(2)));
+        assert!(backoff.next().is_some());
+        assert!(backoff.next().is_some());
+        assert_eq!(backoff.next(), None);
+        assert_eq!(backoff.next(), None);
+    }
+
+    #[test]
+    fn test_backoff_huge_cap() {
+        let max = Duration::new(::core::u64::MAX, 999_999_999);
+        let backoff = Backoff::new(crate::test::rng(1727), max, 2.0, max, 3).unwrap();
+        for delay in backoff {
+            assert!(delay <= Duration::from_nanos(MAX_NANOS));
+            assert!(delay >= Duration::from_nanos(MAX_NANOS / 2));
+        }
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A two-armed Thompson sampler.
+
+use crate::{Beta, Distribution};
+use rand::Rng;
+
+/// A two-armed bandit using Thompson sampling with [`Beta`] posteriors.
+///
+/// The two arms are identified by `false` and `true`. Each arm holds the
+/// parameters `(alpha, beta)` of a `Beta` posterior over its reward rate,
+/// starting from the uniform prior `Beta(1, 1)`. [`BetaBandit::choose`]
+/// samples each posterior and picks the arm with the higher sample, and
+/// [`BetaBandit::update`] records a success (incrementing `alpha`) or a
+/// failure (incrementing `beta`) for an arm. Over time, the arm with the
+/// higher reward rate is chosen increasingly often.
+///
+/// Since the result depends on previous updates, this is not a
+/// [`Distribution`].
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand_distr::BetaBandit;
+///
+/// let mut rng = rand::thread_rng();
+/// let mut bandit = BetaBandit::new();
+/// for _ in 0..100 {
+///     let arm = bandit.choose(&mut rng);
+///     // Arm `true` pays out more often than arm `false`
+///     let reward = rng.gen_bool(if arm { 0.6 } else { 0.4 });
+///     bandit.update(arm, reward);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct BetaBandit {
+    // `(alpha, beta)` for the arms `false` and `true`
+    arms: [(f64, f64); 2],
+}
+
+impl BetaBandit {
+    /// Construct a new `BetaBandit` with a uniform prior on both arms.
+    pub fn new() -> BetaBandit {
+        BetaBandit { arms: [(1.0, 1.0); 2] }
+    }
+
+    /// Choose an arm by sampling both posteriors.
+    ///
+    /// Returns `true` if the sample for arm `true` is higher.
+    pub fn choose<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
+        let mut sample = |(alpha, beta): (f64, f64)| {
+            // Both parameters are at least 1, hence valid
+            Beta::new(alpha, beta).unwrap().sample(rng)
+        };
+        let x_false = sample(self.arms[0]);
+        let x_true = sample(self.arms[1]);
+        x_true > x_false
+    }
+
+    /// Record the outcome of playing `arm`.
+    pub fn update(&mut self, arm: bool, reward: bool) {
+        let (alpha, beta) = &mut self.arms[arm as usize];
+        if reward {
+            *alpha += 1.0;
+        } else {
+            *beta += 1.0;
+        }
+    }
+}
+
+impl Default for BetaBandit {
+    fn default() -> Self {
+        BetaBandit::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_beta_bandit_update() {
+        let mut bandit = BetaBandit::new();
+        bandit.update(true, true);
+        bandit.update(true, false);
+        bandit.update(false, false);
+        assert_eq!(bandit.arms, [(1.0, 2.0), (2.0, 2.0)]);
+    }
+
+    #[test]
+    fn test_beta_bandit_converges() {
+        const ROUNDS: usize = 2000;
+        let mut rng = crate::test::rng(1715);
+        for &(p_false, p_true) in &[(0.3, 0.6), (0.7, 0.4)] {
+            let better = p_true > p_false;
+            let mut bandit = BetaBandit::new();
+            let mut chosen = [0usize; 4];
+            for i in 0..ROUNDS {
+                let arm = bandit.choose(&mut rng);
+                let reward = rng.gen_bool(if arm { p_true } else { p_false });
+                bandit.update(arm, reward);
+                if arm == better {
+                    chosen[i * 4 / ROUNDS] += 1;
+                }
+            }
+            // The better arm is chosen increasingly often
+            assert!(chosen[0] < chosen[3]);
+            assert!(chosen[3] > ROUNDS / 4 * 9 / 10);
+        }
+    }
+}
@@ -123,6 +123,19 @@ mod test {
         assert!((mean - 10.0).abs() > 0.4); // not 100% certain, but probable enough
     }
 
+    #[test]
+    fn test_cauchy_finite() {
+        let mut rng = crate::test::rng(248);
+        let cauchy = Cauchy::new(4.2f32, 6.9).unwrap();
+        for _ in 0..100_000 {
+            assert!(cauchy.sample(&mut rng).is_finite());
+        }
+        let cauchy = Cauchy::new(4.2f64, 6.9).unwrap();
+        for _ in 0..100_000 {
+            assert!(cauchy.sample(&mut rng).is_finite());
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_cauchy_invalid_scale_zero() {
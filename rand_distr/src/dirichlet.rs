@@ -172,6 +172,19 @@ mod test {
             .collect();
     }
 
+    #[test]
+    fn test_dirichlet_sum() {
+        let mut rng = crate::test::rng(249);
+        let d = Dirichlet::new(&[0.1, 1.0, 2.5, 10.0]).unwrap();
+        for _ in 0..100 {
+            let samples: Vec<f64> = d.sample(&mut rng);
+            assert_eq!(samples.len(), 4);
+            assert_almost_eq!(samples.iter().sum::<f64>(), 1.0, 1e-12);
+        }
+        assert_eq!(Dirichlet::<f64>::new(&[]).unwrap_err(), Error::AlphaTooShort);
+        assert_eq!(Dirichlet::new(&[1.0, -1.0]).unwrap_err(), Error::AlphaTooSmall);
+    }
+
     #[test]
     #[should_panic]
     fn test_dirichlet_invalid_length() {
@@ -0,0 +1,143 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A stateful sampler of booleans with a fixed duty cycle.
+
+use rand::Rng;
+use core::fmt;
+
+/// A stateful sampler yielding exactly `k` trues in every window of `n`
+/// consecutive calls, at random positions within the window.
+///
+/// Calls are grouped into consecutive, non-overlapping windows of `n` calls,
+/// starting with the first call to [`DutyCycle::next`]. Within each window,
+/// the positions of the `k` trues are uniformly distributed over all
+/// `n choose k` possibilities. This is achieved by an online shuffle: each
+/// call returns `true` with probability `trues_left / calls_left` for the
+/// current window, hence no buffer is required.
+///
+/// Since the result depends on previous calls, this is not a
+/// [`Distribution`].
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::DutyCycle;
+///
+/// let mut rng = rand::thread_rng();
+/// // On for exactly 3 out of every 10 ticks
+/// let mut pwm = DutyCycle::new(3, 10).unwrap();
+/// let on = (0..10).filter(|_| pwm.next(&mut rng)).count();
+/// assert_eq!(on, 3);
+/// ```
+///
+/// [`Distribution`]: crate::Distribution
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct DutyCycle {
+    k: u32,
+    n: u32,
+    trues_left: u32,
+    calls_left: u32,
+}
+
+/// Error type returned from `DutyCycle::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `n == 0`.
+    WindowEmpty,
+    /// `k > n`.
+    TooManyTrues,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::WindowEmpty => "window size n is zero in duty cycle",
+            Error::TooManyTrues => "k > n in duty cycle",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl DutyCycle {
+    /// Construct a new `DutyCycle` yielding `k` trues in every `n` calls.
+    pub fn new(k: u32, n: u32) -> Result<DutyCycle, Error> {
+        if n == 0 {
+            return Err(Error::WindowEmpty);
+        }
+        if k > n {
+            return Err(Error::TooManyTrues);
+        }
+        Ok(DutyCycle {
+            k,
+            n,
+            trues_left: k,
+            calls_left: n,
+        })
+    }
+
+    /// Sample the next boolean.
+    pub fn next<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
+        if self.calls_left == 0 {
+            self.trues_left = self.k;
+            self.calls_left = self.n;
+        }
+        let result = rng.gen_ratio(self.trues_left, self.calls_left);
+        self.calls_left -= 1;
+        if result {
+            self.trues_left -= 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_duty_cycle_invalid() {
+        assert_eq!(DutyCycle::new(0, 0).unwrap_err(), Error::WindowEmpty);
+        assert_eq!(DutyCycle::new(4, 3).unwrap_err(), Error::TooManyTrues);
+    }
+
+    #[test]
+    fn test_duty_cycle_windows() {
+        let mut rng = crate::test::rng(1688);
+        for &(k, n) in &[(0, 1), (1, 1), (0, 5), (5, 5), (1, 7), (3, 10), (50, 64)] {
+            let mut d = DutyCycle::new(k, n).unwrap();
+            for _ in 0..20 {
+                let trues = (0..n).filter(|_| d.next(&mut rng)).count();
+                assert_eq!(trues, k as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn test_duty_cycle_positions() {
+        // Each position within a window should be true with probability k/n
+        const WINDOWS: usize = 10_000;
+        let mut rng = crate::test::rng(1689);
+        let mut d = DutyCycle::new(2, 8).unwrap();
+        let mut counts = [0usize; 8];
+        for _ in 0..WINDOWS {
+            for c in counts.iter_mut() {
+                if d.next(&mut rng) {
+                    *c += 1;
+                }
+            }
+        }
+        for &c in counts.iter() {
+            assert!((c as f64 - WINDOWS as f64 / 4.0).abs() < 150.0);
+        }
+    }
+}
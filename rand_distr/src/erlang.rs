@@ -0,0 +1,178 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Erlang distribution.
+
+use num_traits::Float;
+use crate::{Distribution, OpenClosed01};
+use rand::Rng;
+use core::fmt;
+
+/// The Erlang distribution `Erlang(k, scale)`.
+///
+/// This is the [`Gamma`] distribution restricted to an integer shape
+/// parameter `k`; equivalently, it is the distribution of the sum of `k`
+/// independent exponential random variables with mean `scale`.
+///
+/// Samples are computed exactly as `-scale * ln(u_1 * ... * u_k)`, where the
+/// `u_i` are independent samples from [`OpenClosed01`]. This requires `k`
+/// uniform samples but only one logarithm (per underflow-safe block), and
+/// for small `k` is both faster and more accurate than the general method
+/// used by [`Gamma`].
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Erlang};
+///
+/// let erlang = Erlang::new(3, 2.0).unwrap();
+/// let v = erlang.sample(&mut rand::thread_rng());
+/// println!("{} is from an Erlang(3, 2) distribution", v);
+/// ```
+///
+/// [`Gamma`]: crate::Gamma
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Erlang<F>
+where F: Float, OpenClosed01: Distribution<F>
+{
+    k: u32,
+    scale: F,
+}
+
+/// Error type returned from `Erlang::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `k == 0`.
+    ShapeTooSmall,
+    /// `scale <= 0` or `nan`.
+    ScaleTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::ShapeTooSmall => "shape k is zero in Erlang distribution",
+            Error::ScaleTooSmall => "scale is not positive in Erlang distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> Erlang<F>
+where F: Float, OpenClosed01: Distribution<F>
+{
+    /// Construct a new `Erlang` distribution with integer shape `k` and the
+    /// given `scale`.
+    pub fn new(k: u32, scale: F) -> Result<Erlang<F>, Error> {
+        if k == 0 {
+            return Err(Error::ShapeTooSmall);
+        }
+        if !(scale > F::zero()) {
+            return Err(Error::ScaleTooSmall);
+        }
+        Ok(Erlang { k, scale })
+    }
+}
+
+impl<F> Distribution<F> for Erlang<F>
+where F: Float, OpenClosed01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        // Accumulate the product of uniforms, folding it into `sum` via a
+        // logarithm whenever it gets small enough to risk underflow.
+        let mut sum = F::zero();
+        let mut product = F::one();
+        for _ in 0..self.k {
+            let u: F = rng.sample(OpenClosed01);
+            product = product * u;
+            if product < F::epsilon() {
+                sum = sum - product.ln();
+                product = F::one();
+            }
+        }
+        (sum - product.ln()) * self.scale
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Gamma;
+
+    #[test]
+    fn test_erlang_invalid() {
+        assert_eq!(Erlang::new(0, 1.0).unwrap_err(), Error::ShapeTooSmall);
+        assert_eq!(Erlang::new(1, 0.0).unwrap_err(), Error::ScaleTooSmall);
+        assert_eq!(Erlang::new(1, -1.0).unwrap_err(), Error::ScaleTooSmall);
+        assert_eq!(Erlang::new(1, core::f64::NAN).unwrap_err(), Error::ScaleTooSmall);
+    }
+
+    #[test]
+    fn test_erlang_large_k() {
+        // The product of 10000 uniforms underflows an f64
+        let erlang = Erlang::new(10000, 1.0).unwrap();
+        let mut rng = crate::test::rng(1679);
+        for _ in 0..10 {
+            let x: f64 = erlang.sample(&mut rng);
+            assert!(x.is_finite());
+            assert!((x - 10000.0).abs() < 500.0);
+        }
+    }
+
+    #[test]
+    fn test_erlang_matches_gamma() {
+        const N: usize = 50_000;
+        fn moments<D: Distribution<f64>>(d: D, seed: u64) -> (f64, f64) {
+            let mut rng = crate::test::rng(seed);
+            let mut sum = 0.0;
+            let mut sum_sq = 0.0;
+            for _ in 0..N {
+                let x = d.sample(&mut rng);
+                sum += x;
+                sum_sq += x * x;
+            }
+            let mean = sum / N as f64;
+            (mean, sum_sq / N as f64 - mean * mean)
+        }
+
+        let (mean, var) = moments(Erlang::new(3, 1.0).unwrap(), 1680);
+        let (gamma_mean, gamma_var) = moments(Gamma::new(3.0, 1.0).unwrap(), 1681);
+        // Gamma(3, 1) has mean 3 and variance 3
+        assert_almost_eq!(mean, 3.0, 0.05);
+        assert_almost_eq!(var, 3.0, 0.15);
+        assert_almost_eq!(mean, gamma_mean, 0.05);
+        assert_almost_eq!(var, gamma_var, 0.2);
+    }
+
+    #[test]
+    fn value_stability() {
+        fn test_samples<F: Float + core::fmt::Debug + core::fmt::Display + core::fmt::LowerExp>(
+            distr: Erlang<F>, thresh: F, expected: &[F], rng: &mut impl Rng,
+        ) where OpenClosed01: Distribution<F> {
+            for v in expected {
+                let x = distr.sample(rng);
+                assert_almost_eq!(x, *v, thresh);
+            }
+        }
+
+        let mut rng = crate::test::rng(213);
+        test_samples(Erlang::new(2, 1.5f32).unwrap(), 1e-6, &[
+            1.1919076, 2.7041926, 1.5526131, 3.4184625,
+        ], &mut rng);
+        test_samples(Erlang::new(5, 0.5f64).unwrap(), 1e-14, &[
+            1.5649765144426415,
+            1.7665264737408357,
+            3.8024226089739637,
+            2.6622954772082643,
+        ], &mut rng);
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The exponentially modified Gaussian distribution.
+
+use num_traits::Float;
+use crate::{Distribution, Exp1, StandardNormal};
+use rand::Rng;
+use core::fmt;
+
+/// The exponentially modified Gaussian (ex-Gaussian) distribution
+/// `ExGaussian(mu, sigma, tau)`.
+///
+/// This is the distribution of the sum of independent samples from
+/// `Normal(mu, sigma)` and from an exponential distribution with mean `tau`
+/// (i.e. rate `1/tau`). It has mean `mu + tau` and variance
+/// `sigma^2 + tau^2`, and is skewed to the right. It is commonly used to
+/// model response times in psychology.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, ExGaussian};
+///
+/// // Response times in milliseconds
+/// let rt = ExGaussian::new(400.0, 40.0, 100.0).unwrap();
+/// let v = rt.sample(&mut rand::thread_rng());
+/// println!("{} ms", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExGaussian<F>
+where F: Float, StandardNormal: Distribution<F>, Exp1: Distribution<F>
+{
+    mu: F,
+    sigma: F,
+    tau: F,
+}
+
+/// Error type returned from `ExGaussian::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `mu` is not finite.
+    MeanNotFinite,
+    /// `sigma < 0` or `sigma` is not finite.
+    BadVariance,
+    /// `tau <= 0` or `tau` is not finite.
+    TauTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::MeanNotFinite => "mu is not finite in ex-Gaussian distribution",
+            Error::BadVariance => "sigma < 0 or is not finite in ex-Gaussian distribution",
+            Error::TauTooSmall => "tau <= 0 or is not finite in ex-Gaussian distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> ExGaussian<F>
+where F: Float, StandardNormal: Distribution<F>, Exp1: Distribution<F>
+{
+    /// Construct a new `ExGaussian` distribution with the given mean `mu`
+    /// and standard deviation `sigma` of the normal component, and mean
+    /// `tau` of the exponential component.
+    pub fn new(mu: F, sigma: F, tau: F) -> Result<ExGaussian<F>, Error> {
+        if !mu.is_finite() {
+            return Err(Error::MeanNotFinite);
+        }
+        if !(sigma >= F::zero()) || !sigma.is_finite() {
+            return Err(Error::BadVariance);
+        }
+        if !(tau > F::zero()) || !tau.is_finite() {
+            return Err(Error::TauTooSmall);
+        }
+        Ok(ExGaussian { mu, sigma, tau })
+    }
+}
+
+impl<F> Distribution<F> for ExGaussian<F>
+where F: Float, StandardNormal: Distribution<F>, Exp1: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let z: F = rng.sample(StandardNormal);
+        let e: F = rng.sample(Exp1);
+        self.mu + self.sigma * z + self.tau * e
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ex_gaussian_invalid() {
+        assert_eq!(ExGaussian::new(core::f64::NAN, 1.0, 1.0).unwrap_err(), Error::MeanNotFinite);
+        assert_eq!(ExGaussian::new(0.0, -1.0, 1.0).unwrap_err(), Error::BadVariance);
+        assert_eq!(ExGaussian::new(0.0, core::f64::NAN, 1.0).unwrap_err(), Error::BadVariance);
+        assert_eq!(ExGaussian::new(0.0, 1.0, 0.0).unwrap_err(), Error::TauTooSmall);
+        assert_eq!(ExGaussian::new(0.0, 1.0, core::f64::INFINITY).unwrap_err(), Error::TauTooSmall);
+        assert!(ExGaussian::new(0.0, 0.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_ex_gaussian_moments() {
+        const N: usize = 100_000;
+        let (mu, sigma, tau) = (400.0, 40.0, 100.0);
+        let distr = ExGaussian::new(mu, sigma, tau).unwrap();
+        let mut rng = crate::test::rng(1712);
+        let mut sum = 0.0;
+        let mut sum_sq = 0.0;
+        let mut sum_cube = 0.0;
+        for _ in 0..N {
+            // Centre on the expected mean for numerical accuracy
+            let d = distr.sample(&mut rng) - (mu + tau);
+            sum += d;
+            sum_sq += d * d;
+            sum_cube += d * d * d;
+        }
+        let m1 = sum / N as f64;
+        let mean = mu + tau + m1;
+        let var = sum_sq / N as f64 - m1 * m1;
+        let m3 = sum_cube / N as f64 - 3.0 * m1 * sum_sq / N as f64 + 2.0 * m1 * m1 * m1;
+        let skewness = m3 / var.powf(1.5);
+
+        // Mean mu + tau, variance sigma^2 + tau^2 and skewness
+        // 2 tau^3 / (sigma^2 + tau^2)^1.5 ~= 1.60
+        assert_almost_eq!(mean, mu + tau, 1.0);
+        assert_almost_eq!(var, sigma * sigma + tau * tau, 300.0);
+        assert!(skewness > 1.3 && skewness < 1.9);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(217);
+        let distr = ExGaussian::new(1.0, 0.5, 2.0).unwrap();
+        let expected = [
+            4.011639322663877,
+            6.646807347507231,
+            2.0796244565310458,
+            1.360107276503689,
+        ];
+        for &v in expected.iter() {
+            let x: f64 = distr.sample(&mut rng);
+            assert_almost_eq!(x, v, 1e-14);
+        }
+    }
+}
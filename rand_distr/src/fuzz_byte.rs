@@ -0,0 +1,130 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A byte distribution biased towards boundary values.
+
+use crate::{Bernoulli, Distribution};
+use rand::Rng;
+use core::fmt;
+
+/// The values preferred by [`FuzzByte`]: integer boundaries and common ASCII
+/// delimiters.
+const SPECIAL: [u8; 10] = [
+    0x00, 0x01, 0x7F, 0x80, 0xFE, 0xFF, b'\n', b' ', b'"', b'%',
+];
+
+/// A distribution of bytes biased towards "interesting" values, for fuzzing.
+///
+/// With probability `special_prob`, one of the following values is sampled
+/// uniformly: the signed and unsigned integer boundaries `0x00`, `0x01`,
+/// `0x7F`, `0x80`, `0xFE` and `0xFF`, and the ASCII delimiters `\n`, space,
+/// `"` and `%`. Otherwise, a uniformly random byte is sampled, hence every
+/// byte remains reachable as long as `special_prob < 1`.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_distr::FuzzByte;
+///
+/// let fuzz = FuzzByte::new(0.25).unwrap();
+/// let input: Vec<u8> = fuzz.sample_iter(thread_rng()).take(16).collect();
+/// println!("{:?}", input);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct FuzzByte {
+    special: Bernoulli,
+}
+
+/// Error type returned from `FuzzByte::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `special_prob < 0` or `special_prob > 1` or `nan`.
+    InvalidProbability,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InvalidProbability => "special_prob is not in [0, 1] in fuzz byte distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl FuzzByte {
+    /// Construct a new `FuzzByte` which samples a special value with
+    /// probability `special_prob`.
+    pub fn new(special_prob: f64) -> Result<FuzzByte, Error> {
+        let special = Bernoulli::new(special_prob).map_err(|_| Error::InvalidProbability)?;
+        Ok(FuzzByte { special })
+    }
+}
+
+impl Distribution<u8> for FuzzByte {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        if self.special.sample(rng) {
+            SPECIAL[rng.gen_range(0..SPECIAL.len())]
+        } else {
+            rng.gen()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fuzz_byte_invalid() {
+        assert_eq!(FuzzByte::new(-0.1).unwrap_err(), Error::InvalidProbability);
+        assert_eq!(FuzzByte::new(1.1).unwrap_err(), Error::InvalidProbability);
+        assert_eq!(FuzzByte::new(core::f64::NAN).unwrap_err(), Error::InvalidProbability);
+    }
+
+    #[test]
+    fn test_fuzz_byte() {
+        const N: usize = 100_000;
+        let mut rng = crate::test::rng(1717);
+        let d = FuzzByte::new(0.3).unwrap();
+        let mut counts = [0usize; 256];
+        for _ in 0..N {
+            counts[d.sample(&mut rng) as usize] += 1;
+        }
+        // Every byte is reachable
+        assert!(counts.iter().all(|&c| c > 0));
+        // Special values are much more common than uniform chance
+        let uniform = N / 256;
+        for &b in &SPECIAL {
+            assert!(counts[b as usize] > 5 * uniform);
+        }
+        let special: usize = SPECIAL.iter().map(|&b| counts[b as usize]).sum();
+        let expected = 0.3 + 0.7 * SPECIAL.len() as f64 / 256.0;
+        assert!((special as f64 / N as f64 - expected).abs() < 0.01);
+
+        // Without special values, samples are uniform
+        let d = FuzzByte::new(0.0).unwrap();
+        let zeros = (0..N).filter(|_| d.sample(&mut rng) == 0).count();
+        assert!((zeros as f64 / N as f64 - 1.0 / 256.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(218);
+        let d = FuzzByte::new(0.5).unwrap();
+        let mut values = [0u8; 8];
+        for x in values.iter_mut() {
+            *x = d.sample(&mut rng);
+        }
+        assert_eq!(values, [114, 91, 33, 32, 0, 255, 126, 32]);
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A capped geometric distribution of levels, as used by skip lists.
+
+use crate::{Bernoulli, Distribution};
+use rand::Rng;
+use core::fmt;
+
+/// Samples levels for skip lists and similar structures: the number of
+/// consecutive successes of trials with probability `p`, capped at
+/// `max_level`.
+///
+/// Level `k < max_level` has probability `p^k * (1 - p)`, so level 0 has
+/// probability `1 - p` and each higher level is `p` times less likely.
+/// Level `max_level` has the remaining probability `p^max_level`.
+///
+/// This takes one Bernoulli trial per level; for a faster uncapped sampler
+/// see [`Geometric`](crate::Geometric).
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, GeometricLevel};
+///
+/// let levels = GeometricLevel::new(0.25, 16).unwrap();
+/// let level = levels.sample(&mut rand::thread_rng());
+/// assert!(level <= 16);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeometricLevel {
+    promote: Bernoulli,
+    max_level: u32,
+}
+
+/// Error type returned from `GeometricLevel::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `p` is not in the range `(0, 1)`.
+    InvalidProbability,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InvalidProbability => "p is not in (0, 1) in geometric level distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl GeometricLevel {
+    /// Construct a new `GeometricLevel` with promotion probability `p` and
+    /// maximum level `max_level`.
+    pub fn new(p: f64, max_level: u32) -> Result<GeometricLevel, Error> {
+        if !(p > 0.0 && p < 1.0) {
+            return Err(Error::InvalidProbability);
+        }
+        let promote = Bernoulli::new(p).map_err(|_| Error::InvalidProbability)?;
+        Ok(GeometricLevel { promote, max_level })
+    }
+}
+
+impl Distribution<u32> for GeometricLevel {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 {
+        let mut level = 0;
+        while level < self.max_level && self.promote.sample(rng) {
+            level += 1;
+        }
+        level
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_geometric_level_invalid() {
+        assert_eq!(GeometricLevel::new(0.0, 10).unwrap_err(), Error::InvalidProbability);
+        assert_eq!(GeometricLevel::new(1.0, 10).unwrap_err(), Error::InvalidProbability);
+        assert_eq!(GeometricLevel::new(core::f64::NAN, 10).unwrap_err(), Error::InvalidProbability);
+        assert!(GeometricLevel::new(0.5, 0).is_ok());
+    }
+
+    #[test]
+    fn test_geometric_level() {
+        const N: usize = 100_000;
+        let mut rng = crate::test::rng(227);
+        let p = 0.25;
+        let distr = GeometricLevel::new(p, 5).unwrap();
+        let mut counts = [0usize; 6];
+        for _ in 0..N {
+            let level = distr.sample(&mut rng);
+            assert!(level <= 5);
+            counts[level as usize] += 1;
+        }
+        let mut expected = 1.0 - p;
+        for &c in counts[..5].iter() {
+            assert_almost_eq!(c as f64 / N as f64, expected, 0.005);
+            expected *= p;
+        }
+        // The capped level takes the remaining probability
+        assert_almost_eq!(counts[5] as f64 / N as f64, p.powi(5), 0.001);
+
+        let distr = GeometricLevel::new(0.9, 0).unwrap();
+        assert!((0..100).all(|_| distr.sample(&mut rng) == 0));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(228);
+        let distr = GeometricLevel::new(0.5, 16).unwrap();
+        let expected = [0, 1, 1, 0, 0, 2, 2, 0];
+        for &v in expected.iter() {
+            assert_eq!(distr.sample(&mut rng), v);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Gompertz distribution.
+
+use num_traits::Float;
+use crate::{Distribution, Standard};
+use rand::Rng;
+use core::fmt;
+
+/// The Gompertz distribution `Gompertz(shape, scale)`.
+///
+/// This is a distribution of lifetimes with hazard (mortality) rate
+/// `scale * exp(shape * x)`, increasing exponentially with age `x`, and is
+/// commonly used in survival analysis and actuarial modelling. Its CDF is
+/// `1 - exp(-(scale / shape) * (exp(shape * x) - 1))` and samples are
+/// non-negative.
+///
+/// Samples are generated via the inverse CDF.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Gompertz};
+///
+/// let lifetime = Gompertz::new(0.1, 0.001).unwrap();
+/// let v = lifetime.sample(&mut rand::thread_rng());
+/// println!("died at age {}", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gompertz<F>
+where F: Float, Standard: Distribution<F>
+{
+    shape: F,
+    shape_div_scale: F,
+}
+
+/// Error type returned from `Gompertz::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `shape <= 0` or `shape` is not finite.
+    ShapeTooSmall,
+    /// `scale <= 0` or `scale` is not finite.
+    ScaleTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::ShapeTooSmall => "shape is not positive and finite in Gompertz distribution",
+            Error::ScaleTooSmall => "scale is not positive and finite in Gompertz distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> Gompertz<F>
+where F: Float, Standard: Distribution<F>
+{
+    /// Construct a new `Gompertz` distribution with the given `shape` (the
+    /// growth rate of the hazard) and `scale` (the hazard at zero).
+    pub fn new(shape: F, scale: F) -> Result<Gompertz<F>, Error> {
+        if !(shape > F::zero()) || !shape.is_finite() {
+            return Err(Error::ShapeTooSmall);
+        }
+        if !(scale > F::zero()) || !scale.is_finite() {
+            return Err(Error::ScaleTooSmall);
+        }
+        Ok(Gompertz { shape, shape_div_scale: shape / scale })
+    }
+}
+
+impl<F> Distribution<F> for Gompertz<F>
+where F: Float, Standard: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(Standard);
+        // (1 / shape) * ln(1 - (shape / scale) * ln(1 - u))
+        (-self.shape_div_scale * (-u).ln_1p()).ln_1p() / self.shape
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gompertz_invalid() {
+        assert_eq!(Gompertz::new(0.0, 1.0).unwrap_err(), Error::ShapeTooSmall);
+        assert_eq!(Gompertz::new(core::f64::NAN, 1.0).unwrap_err(), Error::ShapeTooSmall);
+        assert_eq!(Gompertz::new(1.0, -1.0).unwrap_err(), Error::ScaleTooSmall);
+        assert_eq!(Gompertz::new(1.0, core::f64::INFINITY).unwrap_err(), Error::ScaleTooSmall);
+    }
+
+    #[test]
+    fn test_gompertz_hazard() {
+        const N: usize = 200_000;
+        let (shape, scale) = (0.5, 0.2);
+        let distr = Gompertz::new(shape, scale).unwrap();
+        let mut rng = crate::test::rng(229);
+
+        // Histogram over bins of width 0.5 on [0, 4)
+        const WIDTH: f64 = 0.5;
+        let mut bins = [0usize; 8];
+        let mut beyond = 0;
+        for _ in 0..N {
+            let x = distr.sample(&mut rng);
+            assert!(x >= 0.0);
+            match bins.get_mut((x / WIDTH) as usize) {
+                Some(bin) => *bin += 1,
+                None => beyond += 1,
+            }
+        }
+
+        // Estimate the hazard as the fraction of survivors dying per unit
+        // of time in each bin
+        let mut survivors = N;
+        let mut previous = 0.0;
+        for (i, &deaths) in bins.iter().enumerate() {
+            let hazard = -(1.0 - deaths as f64 / survivors as f64).ln() / WIDTH;
+            // Average of `scale * exp(shape * x)` over the bin
+            let t = i as f64 * WIDTH;
+            let expected = scale / shape * ((shape * (t + WIDTH)).exp() - (shape * t).exp()) / WIDTH;
+            assert!((hazard / expected - 1.0).abs() < 0.05, "bin {}: {} vs {}", i, hazard, expected);
+            assert!(hazard > previous);
+            previous = hazard;
+            survivors -= deaths;
+        }
+        assert_eq!(survivors, beyond);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(230);
+        let distr = Gompertz::new(0.5, 0.2).unwrap();
+        let expected = [
+            2.4447862025733684,
+            2.924932217014314,
+            1.780083919548419,
+            1.8999549983612067,
+        ];
+        for &v in expected.iter() {
+            let x: f64 = distr.sample(&mut rng);
+            assert_almost_eq!(x, v, 1e-14);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Irwin-Hall and Bates distributions.
+
+use num_traits::Float;
+use crate::{Distribution, Standard};
+use rand::Rng;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// The Irwin-Hall distribution `IrwinHall(n)`.
+///
+/// This is the distribution of the sum of `n` independent samples from the
+/// uniform distribution on `[0, 1)` (see [`Standard`]), with support
+/// `[0, n]`, mean `n / 2` and variance `n / 12`. By the central limit theorem
+/// it approaches a normal distribution as `n` grows; the classic cheap
+/// approximation of a standard normal is `IrwinHall(12) - 6`. See also
+/// [`Bates`], the distribution of the mean.
+///
+/// Sampling takes `n` uniform samples.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, IrwinHall};
+///
+/// let approx_normal = IrwinHall::new(12).unwrap();
+/// let v: f64 = approx_normal.sample(&mut rand::thread_rng());
+/// println!("{}", v - 6.0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct IrwinHall<F>
+where F: Float, Standard: Distribution<F>
+{
+    n: u32,
+    phantom: PhantomData<F>,
+}
+
+/// Error type returned from `IrwinHall::new` and `Bates::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `n == 0`.
+    NTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::NTooSmall => "n == 0 in Irwin-Hall or Bates distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> IrwinHall<F>
+where F: Float, Standard: Distribution<F>
+{
+    /// Construct a new `IrwinHall` distribution, summing `n` uniform samples.
+    pub fn new(n: u32) -> Result<IrwinHall<F>, Error> {
+        if n == 0 {
+            return Err(Error::NTooSmall);
+        }
+        Ok(IrwinHall { n, phantom: PhantomData })
+    }
+}
+
+impl<F> Distribution<F> for IrwinHall<F>
+where F: Float, Standard: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let mut sum = F::zero();
+        for _ in 0..self.n {
+            let u: F = rng.sample(Standard);
+            sum = sum + u;
+        }
+        sum
+    }
+}
+
+/// The Bates distribution `Bates(n)`.
+///
+/// This is the distribution of the mean of `n` independent samples from the
+/// uniform distribution on `[0, 1)`, i.e. [`IrwinHall`] divided by `n`. It
+/// has support `[0, 1]`, mean `1 / 2` and variance `1 / (12 n)`.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Bates, Distribution};
+///
+/// let bates = Bates::new(4).unwrap();
+/// let v: f64 = bates.sample(&mut rand::thread_rng());
+/// assert!(v >= 0.0 && v <= 1.0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bates<F>
+where F: Float, Standard: Distribution<F>
+{
+    irwin_hall: IrwinHall<F>,
+    inv_n: F,
+}
+
+impl<F> Bates<F>
+where F: Float, Standard: Distribution<F>
+{
+    /// Construct a new `Bates` distribution, averaging `n` uniform samples.
+    pub fn new(n: u32) -> Result<Bates<F>, Error> {
+        Ok(Bates {
+            irwin_hall: IrwinHall::new(n)?,
+            inv_n: F::one() / F::from(n).unwrap(),
+        })
+    }
+}
+
+impl<F> Distribution<F> for Bates<F>
+where F: Float, Standard: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        self.irwin_hall.sample(rng) * self.inv_n
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_irwin_hall_invalid() {
+        assert_eq!(IrwinHall::<f64>::new(0).unwrap_err(), Error::NTooSmall);
+        assert_eq!(Bates::<f64>::new(0).unwrap_err(), Error::NTooSmall);
+    }
+
+    #[test]
+    fn test_irwin_hall_normal_approximation() {
+        const N: usize = 100_000;
+        let distr = IrwinHall::new(12).unwrap();
+        let mut rng = crate::test::rng(242);
+        let mut sum = 0.0;
+        let mut sum_sq = 0.0;
+        let mut below_one = 0;
+        for _ in 0..N {
+            let x: f64 = distr.sample(&mut rng);
+            assert!((0.0..=12.0).contains(&x));
+            let z = x - 6.0;
+            sum += z;
+            sum_sq += z * z;
+            if z < 1.0 {
+                below_one += 1;
+            }
+        }
+        assert_almost_eq!(sum / N as f64, 0.0, 0.01);
+        assert_almost_eq!(sum_sq / N as f64, 1.0, 0.02);
+        // Phi(1) = 0.8413
+        assert_almost_eq!(below_one as f64 / N as f64, 0.8413, 0.005);
+    }
+
+    #[test]
+    fn test_bates() {
+        const N: usize = 100_000;
+        let mut rng = crate::test::rng(243);
+        for &n in [1, 3, 20].iter() {
+            let distr = Bates::new(n).unwrap();
+            let mut sum = 0.0;
+            let mut sum_sq = 0.0;
+            for _ in 0..N {
+                let x: f64 = distr.sample(&mut rng);
+                assert!((0.0..=1.0).contains(&x));
+                sum += x - 0.5;
+                sum_sq += (x - 0.5) * (x - 0.5);
+            }
+            assert_almost_eq!(sum / N as f64, 0.0, 0.005);
+            let var = 1.0 / (12.0 * n as f64);
+            assert_almost_eq!(sum_sq / N as f64, var, 0.03 * var);
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(244);
+        let expected = [
+            1.703032845729434,
+            1.0533282058353284,
+            0.5786614779916324,
+            0.3441855879654723,
+        ];
+        let irwin_hall = IrwinHall::new(3).unwrap();
+        let bates = Bates::new(3).unwrap();
+        for (i, &v) in expected.iter().enumerate() {
+            let x: f64 = if i < 2 {
+                irwin_hall.sample(&mut rng)
+            } else {
+                bates.sample(&mut rng)
+            };
+            assert_almost_eq!(x, v, 1e-14);
+        }
+    }
+}
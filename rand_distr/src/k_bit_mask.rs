@@ -0,0 +1,126 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Random 64-bit masks with a fixed number of bits set.
+
+use crate::Distribution;
+use rand::Rng;
+use core::fmt;
+
+/// Samples `u64` values with exactly `k` bits set, uniformly among all such
+/// values.
+///
+/// Each output represents a uniformly random `k`-subset of a 64-element set,
+/// so each bit is set with probability `k / 64`. The set bits are chosen by
+/// a partial Fisher-Yates shuffle of the 64 bit positions; where `k > 32`,
+/// the `64 - k` clear bits are chosen instead, so at most 32 random indices
+/// are sampled.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, KBitMask};
+///
+/// let masks = KBitMask::new(5).unwrap();
+/// let mask = masks.sample(&mut rand::thread_rng());
+/// assert_eq!(mask.count_ones(), 5);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct KBitMask {
+    k: u32,
+}
+
+/// Error type returned from `KBitMask::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `k > 64`.
+    TooManyBits,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::TooManyBits => "k is greater than 64 in k-bit mask distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl KBitMask {
+    /// Construct a new `KBitMask` sampling masks with `k` bits set.
+    pub fn new(k: u32) -> Result<KBitMask, Error> {
+        if k > 64 {
+            return Err(Error::TooManyBits);
+        }
+        Ok(KBitMask { k })
+    }
+}
+
+impl Distribution<u64> for KBitMask {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        let invert = self.k > 32;
+        let picks = if invert { 64 - self.k } else { self.k } as usize;
+
+        let mut positions = [0u8; 64];
+        for (i, p) in positions.iter_mut().enumerate() {
+            *p = i as u8;
+        }
+        let mut mask = 0u64;
+        for i in 0..picks {
+            positions.swap(i, rng.gen_range(i..64));
+            mask |= 1 << positions[i];
+        }
+        if invert { !mask } else { mask }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_k_bit_mask_invalid() {
+        assert_eq!(KBitMask::new(65).unwrap_err(), Error::TooManyBits);
+        assert!(KBitMask::new(64).is_ok());
+    }
+
+    #[test]
+    fn test_k_bit_mask() {
+        const N: u32 = 20_000;
+        let mut rng = crate::test::rng(246);
+        for &k in &[0u32, 1, 7, 32, 40, 63, 64] {
+            let distr = KBitMask::new(k).unwrap();
+            let mut counts = [0u32; 64];
+            for _ in 0..N {
+                let mask = distr.sample(&mut rng);
+                assert_eq!(mask.count_ones(), k);
+                for (bit, count) in counts.iter_mut().enumerate() {
+                    *count += (mask >> bit) as u32 & 1;
+                }
+            }
+            let p = k as f64 / 64.0;
+            for &count in counts.iter() {
+                assert_almost_eq!(count as f64 / N as f64, p, 0.015);
+            }
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(247);
+        let distr = KBitMask::new(3).unwrap();
+        let expected = [0x2080020000000000, 0x20000002000040, 0x80000000300];
+        for &v in expected.iter() {
+            assert_eq!(distr.sample(&mut rng), v);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A leaky bucket rate limiter with randomized admission.
+
+use rand::Rng;
+use core::fmt;
+
+/// A leaky bucket rate limiter, deciding randomly whether to admit requests
+/// near capacity.
+///
+/// The bucket holds a non-negative level, which drains at `leak_rate` units
+/// per unit of time. Each admitted request adds one unit.
+/// A request is always admitted if it fits (the level is at most
+/// `capacity - 1`), never if the bucket is full, and in between with
+/// probability `capacity - level`. This jitter avoids a hard threshold
+/// between admitting and rejecting. A request admitted this way still adds
+/// one unit, so the level may exceed `capacity`, though always by less than
+/// one. Under sustained load the admission rate converges to `leak_rate`.
+///
+/// Since the result depends on previous calls, this is not a
+/// [`Distribution`](crate::Distribution).
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::LeakyBucket;
+///
+/// let mut rng = rand::thread_rng();
+/// // Allow bursts of 10 requests and 2 requests per second on average
+/// let mut bucket = LeakyBucket::new(10.0, 2.0).unwrap();
+/// let admitted = (0..100)
+///     .filter(|&i| bucket.admit(&mut rng, i as f64 * 0.1))
+///     .count();
+/// println!("admitted {} of 100 requests", admitted);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeakyBucket {
+    capacity: f64,
+    leak_rate: f64,
+    level: f64,
+    last: Option<f64>,
+}
+
+/// Error type returned from `LeakyBucket::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `capacity < 1` or `capacity` is not finite.
+    CapacityTooSmall,
+    /// `leak_rate <= 0` or `leak_rate` is not finite.
+    LeakRateTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::CapacityTooSmall => "capacity < 1 or is not finite in leaky bucket",
+            Error::LeakRateTooSmall => "leak_rate <= 0 or is not finite in leaky bucket",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl LeakyBucket {
+    /// Construct a new, empty `LeakyBucket` with the given `capacity`,
+    /// draining `leak_rate` requests per unit of time.
+    ///
+    /// Requests are only admitted while the level is below `capacity`, so
+    /// the level stays below `capacity + 1`.
+    pub fn new(capacity: f64, leak_rate: f64) -> Result<LeakyBucket, Error> {
+        if !(capacity >= 1.0) || !capacity.is_finite() {
+            return Err(Error::CapacityTooSmall);
+        }
+        if !(leak_rate > 0.0) || !leak_rate.is_finite() {
+            return Err(Error::LeakRateTooSmall);
+        }
+        Ok(LeakyBucket { capacity, leak_rate, level: 0.0, last: None })
+    }
+
+    /// Decide whether to admit a request arriving at time `now`.
+    ///
+    /// The bucket first leaks for the time elapsed since the previous call.
+    /// Times should be non-decreasing; a time before the previous call is
+    /// treated as no time having elapsed.
+    pub fn admit<R: Rng + ?Sized>(&mut self, rng: &mut R, now: f64) -> bool {
+        match self.last {
+            Some(last) if !(now > last) => (),
+            Some(last) => {
+                self.level = (self.level - (now - last) * self.leak_rate).max(0.0);
+                self.last = Some(now);
+            }
+            None => self.last = Some(now),
+        }
+
+        let room = self.capacity - self.level;
+        let admit = if room >= 1.0 {
+            true
+        } else if room > 0.0 {
+            rng.gen::<f64>() < room
+        } else {
+            false
+        };
+        if admit {
+            self.level += 1.0;
+        }
+        admit
+    }
+
+    /// Return the current level of the bucket, as of the previous call to
+    /// [`LeakyBucket::admit`].
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_leaky_bucket_invalid() {
+        assert_eq!(LeakyBucket::new(0.5, 1.0).unwrap_err(), Error::CapacityTooSmall);
+        assert_eq!(LeakyBucket::new(core::f64::NAN, 1.0).unwrap_err(), Error::CapacityTooSmall);
+        assert_eq!(LeakyBucket::new(core::f64::INFINITY, 1.0).unwrap_err(), Error::CapacityTooSmall);
+        assert_eq!(LeakyBucket::new(1.0, 0.0).unwrap_err(), Error::LeakRateTooSmall);
+        assert_eq!(LeakyBucket::new(1.0, core::f64::NAN).unwrap_err(), Error::LeakRateTooSmall);
+    }
+
+    #[test]
+    fn test_leaky_bucket_burst() {
+        let mut rng = crate::test::rng(225);
+        let mut bucket = LeakyBucket::new(5.0, 1.0).unwrap();
+        // An empty bucket admits a burst of `capacity` requests, then is full
+        assert!((0..5).all(|_| bucket.admit(&mut rng, 0.0)));
+        assert!(!bucket.admit(&mut rng, 0.0));
+        assert_eq!(bucket.level(), 5.0);
+        // After leaking for two units of time two more requests fit
+        assert!(bucket.admit(&mut rng, 2.0));
+        assert!(bucket.admit(&mut rng, 2.0));
+        assert!(!bucket.admit(&mut rng, 2.0));
+        // Going back in time does not leak
+        assert!(!bucket.admit(&mut rng, 1.0));
+        assert!(!bucket.admit(&mut rng, 2.0));
+    }
+
+    #[test]
+    fn test_leaky_bucket_rate() {
+        let mut rng = crate::test::rng(226);
+        let (leak_rate, arrival_rate, duration) = (3.0, 10.0, 10_000.0);
+        let mut bucket = LeakyBucket::new(4.5, leak_rate).unwrap();
+        let mut admitted = 0;
+        let mut jittered = 0;
+        let n = (arrival_rate * duration) as usize;
+        for i in 0..n {
+            let now = i as f64 / arrival_rate;
+            let before = bucket.level();
+            if bucket.admit(&mut rng, now) {
+                admitted += 1;
+                if bucket.level() > 4.5 {
+                    jittered += 1;
+                }
+            }
+            assert!(before < 5.5);
+            assert!(bucket.level() < 5.5);
+        }
+        let rate = admitted as f64 / duration;
+        assert!((rate - leak_rate).abs() < 0.01, "rate: {}", rate);
+        // Some requests were admitted randomly near capacity
+        assert!(jittered > 0);
+    }
+}
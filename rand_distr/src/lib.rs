@@ -44,35 +44,60 @@
 //!   (e.g. errors, offsets):
 //!   - [`Normal`] distribution, and [`StandardNormal`] as a primitive
 //!   - [`Cauchy`] distribution
+//!   - [`ExGaussian`] distribution, the sum of normal and exponential samples
+//!   - [`Stable`] (Lévy alpha-stable) distribution, with heavy tails
+//!   - [`TukeyLambda`] distribution, a symmetric family with variable tails
 //! - Related to Bernoulli trials (yes/no events, with a given probability):
 //!   - [`Binomial`] distribution
 //!   - [`Geometric`] distribution
+//!   - [`GeometricLevel`] distribution, the capped levels used by skip lists
 //!   - [`Hypergeometric`] distribution
+//!   - [`KBitMask`] distribution, over 64-bit masks with `k` bits set
+//!   - [`DutyCycle`] sampler, yielding exactly `k` trues in every `n` calls
+//!   - [`RunLengthBool`] sampler, yielding runs of a given expected length
+//!   - [`BetaBandit`] two-armed Thompson sampler, using [`Beta`] posteriors
 //! - Related to positive real-valued quantities that grow exponentially
 //!   (e.g. prices, incomes, populations):
 //!   - [`LogNormal`] distribution
+//!   - [`LogSpace`] distribution, uniform in log-space
 //! - Related to the occurrence of independent events at a given rate:
 //!   - [`Pareto`] distribution
+//!   - [`GeneralizedPareto`] distribution, for peaks over a threshold
 //!   - [`Poisson`] distribution
 //!   - [`Exp`]onential distribution, and [`Exp1`] as a primitive
 //!   - [`Weibull`] distribution
+//!   - [`Gompertz`] distribution, with exponentially increasing hazard
 //! - Gamma and derived distributions:
 //!   - [`Gamma`] distribution
+//!   - [`Erlang`] distribution
 //!   - [`ChiSquared`] distribution
 //!   - [`StudentT`] distribution
+//!   - [`NoncentralT`] distribution
 //!   - [`FisherF`] distribution
+//!   - [`Nakagami`] distribution, modelling fading signal amplitudes
 //! - Triangular distribution:
 //!   - [`Beta`] distribution
 //!   - [`Triangular`] distribution
+//!   - [`SymTriangularInt`] distribution
+//!   - [`Arcsine`] distribution
+//!   - [`IrwinHall`] and [`Bates`] distributions, the sum and mean of uniforms
 //! - Multivariate probability distributions
 //!   - [`Dirichlet`] distribution
 //!   - [`UnitSphere`] distribution
 //!   - [`UnitBall`] distribution
 //!   - [`UnitCircle`] distribution
 //!   - [`UnitDisc`] distribution
+//!   - [`UnitQuaternion`] distribution, for uniformly random rotations
 //! - Alternative implementation for weighted index sampling
 //!   - [`WeightedAliasIndex`] distribution
+//!   - [`IntWeightedIndex`] distribution
 //! - Misc. distributions
+//!   - [`Mixture`] of weighted distributions
+//!   - [`PiecewiseLinear`] distribution, defined by control points of its CDF
+//!   - [`FuzzByte`] distribution, biased towards boundary values for fuzzing
+//!   - [`UnicodeBlock`] distribution over the characters of a Unicode block
+//!   - [`Backoff`] iterator over randomized exponential retry delays
+//!   - [`LeakyBucket`] rate limiter with randomized admission near capacity
 //!   - [`InverseGaussian`] distribution
 //!   - [`NormalInverseGaussian`] distribution
 
@@ -91,28 +116,50 @@ pub use rand::distributions::{
     Standard, Uniform,
 };
 
+pub use self::arcsine::Arcsine;
+pub use self::backoff::{Backoff, Error as BackoffError};
+pub use self::beta_bandit::BetaBandit;
 pub use self::binomial::{Binomial, Error as BinomialError};
 pub use self::cauchy::{Cauchy, Error as CauchyError};
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub use self::dirichlet::{Dirichlet, Error as DirichletError};
+pub use self::duty_cycle::{DutyCycle, Error as DutyCycleError};
+pub use self::erlang::{Erlang, Error as ErlangError};
+pub use self::ex_gaussian::{Error as ExGaussianError, ExGaussian};
 pub use self::exponential::{Error as ExpError, Exp, Exp1};
+pub use self::fuzz_byte::{Error as FuzzByteError, FuzzByte};
 pub use self::gamma::{
     Beta, BetaError, ChiSquared, ChiSquaredError, Error as GammaError, FisherF, FisherFError,
     Gamma, StudentT,
 };
+pub use self::generalized_pareto::{Error as GeneralizedParetoError, GeneralizedPareto};
 pub use self::geometric::{Error as GeoError, Geometric, StandardGeometric};
+pub use self::geometric_level::{Error as GeometricLevelError, GeometricLevel};
+pub use self::gompertz::{Error as GompertzError, Gompertz};
 pub use self::hypergeometric::{Error as HyperGeoError, Hypergeometric};
 pub use self::inverse_gaussian::{InverseGaussian, Error as InverseGaussianError};
+pub use self::irwin_hall::{Bates, Error as IrwinHallError, IrwinHall};
+pub use self::k_bit_mask::{Error as KBitMaskError, KBitMask};
+pub use self::leaky_bucket::{Error as LeakyBucketError, LeakyBucket};
+pub use self::log_space::{Error as LogSpaceError, LogSpace};
+pub use self::nakagami::{Error as NakagamiError, Nakagami};
+pub use self::noncentral_t::{Error as NoncentralTError, NoncentralT};
 pub use self::normal::{Error as NormalError, LogNormal, Normal, StandardNormal};
 pub use self::normal_inverse_gaussian::{NormalInverseGaussian, Error as NormalInverseGaussianError};
 pub use self::pareto::{Error as ParetoError, Pareto};
 pub use self::pert::{Pert, PertError};
 pub use self::poisson::{Error as PoissonError, Poisson};
+pub use self::run_length_bool::{Error as RunLengthBoolError, RunLengthBool};
+pub use self::stable::{Error as StableError, Stable};
+pub use self::sym_triangular_int::{Error as SymTriangularIntError, SymTriangularInt};
 pub use self::triangular::{Triangular, TriangularError};
+pub use self::tukey_lambda::{Error as TukeyLambdaError, TukeyLambda};
+pub use self::unicode_block::{Error as UnicodeBlockError, UnicodeBlock};
 pub use self::unit_ball::UnitBall;
 pub use self::unit_circle::UnitCircle;
 pub use self::unit_disc::UnitDisc;
+pub use self::unit_quaternion::UnitQuaternion;
 pub use self::unit_sphere::UnitSphere;
 pub use self::weibull::{Error as WeibullError, Weibull};
 #[cfg(feature = "alloc")]
@@ -121,6 +168,15 @@ pub use rand::distributions::{WeightedError, WeightedIndex};
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub use weighted_alias::WeightedAliasIndex;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use weighted_int::IntWeightedIndex;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use mixture::{DynDistribution, Mixture};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use piecewise_linear::{Error as PiecewiseLinearError, PiecewiseLinear};
 
 pub use num_traits;
 
@@ -177,25 +233,53 @@ mod test {
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub mod weighted_alias;
 
+mod arcsine;
+mod backoff;
+mod beta_bandit;
 mod binomial;
 mod cauchy;
 mod dirichlet;
+mod duty_cycle;
+mod erlang;
+mod ex_gaussian;
 mod exponential;
+mod fuzz_byte;
 mod gamma;
+mod generalized_pareto;
 mod geometric;
+mod geometric_level;
+mod gompertz;
 mod hypergeometric;
 mod inverse_gaussian;
+mod irwin_hall;
+mod k_bit_mask;
+mod leaky_bucket;
+mod log_space;
+#[cfg(feature = "alloc")]
+mod mixture;
+mod nakagami;
+mod noncentral_t;
 mod normal;
 mod normal_inverse_gaussian;
 mod pareto;
 mod pert;
+#[cfg(feature = "alloc")]
+mod piecewise_linear;
 mod poisson;
+mod run_length_bool;
+mod stable;
+mod sym_triangular_int;
 mod triangular;
+mod tukey_lambda;
+mod unicode_block;
 mod unit_ball;
 mod unit_circle;
 mod unit_disc;
+mod unit_quaternion;
 mod unit_sphere;
 mod utils;
 mod weibull;
+#[cfg(feature = "alloc")]
+mod weighted_int;
 mod ziggurat_tables;
 
@@ -0,0 +1,162 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The log-uniform distribution.
+
+use num_traits::Float;
+use crate::{Distribution, Standard};
+use rand::Rng;
+use core::fmt;
+
+/// The log-uniform distribution `LogSpace(low, high)`.
+///
+/// Samples are uniformly distributed in log-space between `low` and `high`,
+/// i.e. each octave (or decade) in the range is equally likely. This is
+/// useful for example to pick test frequencies for audio. Samples are
+/// computed as `exp(ln(low) + u * (ln(high) - ln(low)))` where `u` is sampled
+/// from [`Standard`], and are clamped to `[low, high]` to guard against
+/// rounding errors.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, LogSpace};
+///
+/// // Audible frequencies in Hz
+/// let freq = LogSpace::new(20.0, 20_000.0).unwrap();
+/// let v = freq.sample(&mut rand::thread_rng());
+/// println!("{} Hz is from a log-uniform distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogSpace<F>
+where F: Float, Standard: Distribution<F>
+{
+    low: F,
+    high: F,
+    log_low: F,
+    log_range: F,
+}
+
+/// Error type returned from `LogSpace::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `low <= 0` or `nan`.
+    LowNotPositive,
+    /// `high` is infinite or `nan`.
+    HighNotFinite,
+    /// `low >= high`.
+    EmptyRange,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::LowNotPositive => "low is not positive in log-uniform distribution",
+            Error::HighNotFinite => "high is not finite in log-uniform distribution",
+            Error::EmptyRange => "low >= high in log-uniform distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> LogSpace<F>
+where F: Float, Standard: Distribution<F>
+{
+    /// Construct a new `LogSpace` distribution over `[low, high]`.
+    pub fn new(low: F, high: F) -> Result<LogSpace<F>, Error> {
+        if !(low > F::zero()) {
+            return Err(Error::LowNotPositive);
+        }
+        if !high.is_finite() {
+            return Err(Error::HighNotFinite);
+        }
+        if !(low < high) {
+            return Err(Error::EmptyRange);
+        }
+        let (log_low, log_high) = (low.ln(), high.ln());
+        if !(log_low < log_high) {
+            // `low` and `high` are too close to be distinguished in log-space
+            return Err(Error::EmptyRange);
+        }
+        Ok(LogSpace {
+            low,
+            high,
+            log_low,
+            log_range: log_high - log_low,
+        })
+    }
+}
+
+impl<F> Distribution<F> for LogSpace<F>
+where F: Float, Standard: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(Standard);
+        let x = (self.log_low + self.log_range * u).exp();
+        x.max(self.low).min(self.high)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_log_space_invalid() {
+        assert_eq!(LogSpace::new(0.0, 1.0).unwrap_err(), Error::LowNotPositive);
+        assert_eq!(LogSpace::new(-1.0, 1.0).unwrap_err(), Error::LowNotPositive);
+        assert_eq!(LogSpace::new(core::f64::NAN, 1.0).unwrap_err(), Error::LowNotPositive);
+        assert_eq!(LogSpace::new(1.0, core::f64::INFINITY).unwrap_err(), Error::HighNotFinite);
+        assert_eq!(LogSpace::new(1.0, core::f64::NAN).unwrap_err(), Error::HighNotFinite);
+        assert_eq!(LogSpace::new(2.0, 1.0).unwrap_err(), Error::EmptyRange);
+        assert_eq!(LogSpace::new(1.0, 1.0).unwrap_err(), Error::EmptyRange);
+    }
+
+    #[test]
+    fn test_log_space() {
+        const N: usize = 20_000;
+        let (low, high) = (20.0, 20_000.0);
+        let distr = LogSpace::new(low, high).unwrap();
+        let mut rng = crate::test::rng(1692);
+        let mut log_sum = 0.0;
+        let mut below_sqrt = 0;
+        let geo_mean: f64 = (low * high).sqrt();
+        for _ in 0..N {
+            let x: f64 = distr.sample(&mut rng);
+            assert!((low..=high).contains(&x));
+            log_sum += x.ln();
+            if x < geo_mean {
+                below_sqrt += 1;
+            }
+        }
+        let mean = (log_sum / N as f64).exp();
+        assert!((mean / geo_mean - 1.0).abs() < 0.05);
+        // Half the mass lies below the geometric mean
+        assert!((below_sqrt as f64 / N as f64 - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn value_stability() {
+        let distr = LogSpace::new(1.0f64, 1000.0).unwrap();
+        let mut rng = crate::test::rng(216);
+        let expected = [
+            310.5754342459044,
+            77.86587986455673,
+            25.868573484413055,
+            95.59967553538216,
+        ];
+        for &v in expected.iter() {
+            let x: f64 = distr.sample(&mut rng);
+            assert_almost_eq!(x, v, 1e-11);
+        }
+    }
+}
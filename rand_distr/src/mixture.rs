@@ -0,0 +1,143 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Mixtures of distributions.
+
+use crate::{Distribution, WeightedError, WeightedIndex};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use rand::{Rng, RngCore};
+
+/// An object-safe counterpart of [`Distribution`].
+///
+/// [`Distribution::sample`] is generic over the RNG type, so
+/// `dyn DynDistribution<T>` is not a valid type. This trait is implemented for
+/// every `Distribution<T>` and may be used as `Box<dyn DynDistribution<T>>`
+/// to hold distributions of differing types, e.g. as the components of a
+/// [`Mixture`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub trait DynDistribution<T> {
+    /// Generate a random value of `T`, using a dynamically-typed `rng`.
+    fn sample_dyn(&self, rng: &mut dyn RngCore) -> T;
+}
+
+impl<T, D: Distribution<T>> DynDistribution<T> for D {
+    fn sample_dyn(&self, rng: &mut dyn RngCore) -> T {
+        self.sample(rng)
+    }
+}
+
+/// A mixture of weighted component distributions.
+///
+/// Each sample first selects one of the components, with probability
+/// proportional to its weight (via [`WeightedIndex`]), then returns a sample
+/// from the selected component. Mixtures may be used to model multimodal
+/// data.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, DynDistribution, Mixture, Normal};
+///
+/// // Heights of a population which is 40% children and 60% adults
+/// let heights = Mixture::new(vec![
+///     (0.4, Box::new(Normal::new(120.0, 15.0).unwrap()) as Box<dyn DynDistribution<f64>>),
+///     (0.6, Box::new(Normal::new(172.0, 9.0).unwrap())),
+/// ]).unwrap();
+/// let v = heights.sample(&mut rand::thread_rng());
+/// println!("{} cm", v);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct Mixture<T> {
+    weights: WeightedIndex<f64>,
+    components: Vec<Box<dyn DynDistribution<T>>>,
+}
+
+impl<T> Mixture<T> {
+    /// Construct a new `Mixture` from a list of `(weight, component)` pairs.
+    ///
+    /// Returns an error if `components` is empty, if any weight is negative
+    /// or NaN, or if all weights are zero; see [`WeightedIndex::new`].
+    pub fn new(components: Vec<(f64, Box<dyn DynDistribution<T>>)>) -> Result<Mixture<T>, WeightedError> {
+        let weights = WeightedIndex::new(components.iter().map(|c| c.0))?;
+        let components = components.into_iter().map(|c| c.1).collect();
+        Ok(Mixture { weights, components })
+    }
+}
+
+impl<T> Distribution<T> for Mixture<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        let index = self.weights.sample(rng);
+        // `R` may be unsized, but `&mut R` is not
+        self.components[index].sample_dyn(&mut &mut *rng)
+    }
+}
+
+impl<T> fmt::Debug for Mixture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mixture")
+            .field("weights", &self.weights)
+            .field("components", &self.components.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Normal;
+    use alloc::vec;
+
+    #[test]
+    fn test_mixture_invalid() {
+        assert_eq!(
+            Mixture::<f64>::new(vec![]).unwrap_err(),
+            WeightedError::NoItem
+        );
+        let zero: Vec<(f64, Box<dyn DynDistribution<f64>>)> = vec![
+            (0.0, Box::new(Normal::new(0.0, 1.0).unwrap())),
+            (0.0, Box::new(Normal::new(1.0, 1.0).unwrap())),
+        ];
+        assert_eq!(Mixture::new(zero).unwrap_err(), WeightedError::AllWeightsZero);
+        let negative: Vec<(f64, Box<dyn DynDistribution<f64>>)> =
+            vec![(-1.0, Box::new(Normal::new(0.0, 1.0).unwrap()))];
+        assert_eq!(Mixture::new(negative).unwrap_err(), WeightedError::InvalidWeight);
+    }
+
+    #[test]
+    fn test_mixture_bimodal() {
+        const N: usize = 20_000;
+        let mixture = Mixture::new(vec![
+            (1.0, Box::new(Normal::new(-10.0, 1.0).unwrap()) as Box<dyn DynDistribution<f64>>),
+            (3.0, Box::new(Normal::new(10.0, 1.0).unwrap())),
+        ])
+        .unwrap();
+
+        // Histogram over [-15, 15) in bins of width 2
+        let mut rng = crate::test::rng(1698);
+        let mut bins = [0usize; 15];
+        for _ in 0..N {
+            let x = mixture.sample(&mut rng);
+            let bin = ((x + 15.0) / 2.0).floor();
+            if (0.0..15.0).contains(&bin) {
+                bins[bin as usize] += 1;
+            }
+        }
+
+        // Modes at -10 (bin 2) and 10 (bin 12) with nothing in between
+        assert!(bins[2] > bins[1] && bins[2] > bins[3]);
+        assert!(bins[12] > bins[11] && bins[12] > bins[13]);
+        assert!(bins[5..10].iter().all(|&c| c == 0));
+        // Component proportions are 1:3
+        let low: usize = bins[..7].iter().sum();
+        let high: usize = bins[8..].iter().sum();
+        assert!((low as f64 / N as f64 - 0.25).abs() < 0.01);
+        assert!((high as f64 / N as f64 - 0.75).abs() < 0.01);
+    }
+}
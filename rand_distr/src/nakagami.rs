@@ -0,0 +1,168 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Nakagami-m distribution.
+
+use num_traits::Float;
+use crate::{Distribution, Exp1, Gamma, Open01, StandardNormal};
+use rand::Rng;
+use core::fmt;
+
+/// The Nakagami-m distribution `Nakagami(m, Ω)` with shape `m` and spread
+/// `Ω`.
+///
+/// This is the distribution of `sqrt(X)` where `X` is sampled from
+/// `Gamma(m, Ω / m)`, and is used to model the amplitude of fading wireless
+/// signals. The mean square is `Ω`. With `m = 1` it is a Rayleigh
+/// distribution with scale `sqrt(Ω / 2)`; larger `m` means less severe
+/// fading.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Nakagami};
+///
+/// let fading = Nakagami::new(2.0, 1.0).unwrap();
+/// let amplitude = fading.sample(&mut rand::thread_rng());
+/// println!("{}", amplitude);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Nakagami<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+    Exp1: Distribution<F>,
+    Open01: Distribution<F>,
+{
+    gamma: Gamma<F>,
+}
+
+/// Error type returned from `Nakagami::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `m < 0.5` or `nan`.
+    ShapeTooSmall,
+    /// `omega <= 0` or `omega` is not finite.
+    SpreadTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::ShapeTooSmall => "shape m < 0.5 or is NaN in Nakagami distribution",
+            Error::SpreadTooSmall => "spread omega is not positive and finite in Nakagami distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> Nakagami<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+    Exp1: Distribution<F>,
+    Open01: Distribution<F>,
+{
+    /// Construct a new `Nakagami` distribution with shape `m` and spread
+    /// `omega`.
+    pub fn new(m: F, omega: F) -> Result<Nakagami<F>, Error> {
+        if !(m >= F::from(0.5).unwrap()) || !m.is_finite() {
+            return Err(Error::ShapeTooSmall);
+        }
+        if !(omega > F::zero()) || !omega.is_finite() {
+            return Err(Error::SpreadTooSmall);
+        }
+        let gamma = Gamma::new(m, omega / m).map_err(|_| Error::SpreadTooSmall)?;
+        Ok(Nakagami { gamma })
+    }
+}
+
+impl<F> Distribution<F> for Nakagami<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+    Exp1: Distribution<F>,
+    Open01: Distribution<F>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        self.gamma.sample(rng).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nakagami_invalid() {
+        assert_eq!(Nakagami::new(0.4, 1.0).unwrap_err(), Error::ShapeTooSmall);
+        assert_eq!(Nakagami::new(core::f64::NAN, 1.0).unwrap_err(), Error::ShapeTooSmall);
+        assert_eq!(Nakagami::new(1.0, 0.0).unwrap_err(), Error::SpreadTooSmall);
+        assert_eq!(Nakagami::new(1.0, core::f64::INFINITY).unwrap_err(), Error::SpreadTooSmall);
+        assert!(Nakagami::new(0.5, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_nakagami_mean_square() {
+        const N: usize = 100_000;
+        let mut rng = crate::test::rng(231);
+        for &(m, omega) in [(0.5, 1.0), (1.0, 3.0), (4.5, 0.2)].iter() {
+            let distr = Nakagami::new(m, omega).unwrap();
+            let mut sum_sq = 0.0;
+            for _ in 0..N {
+                let x = distr.sample(&mut rng);
+                assert!(x >= 0.0);
+                sum_sq += x * x;
+            }
+            assert_almost_eq!(sum_sq / N as f64, omega, 0.02 * omega);
+        }
+    }
+
+    #[test]
+    fn test_nakagami_rayleigh() {
+        // With m = 1 the CDF is that of Rayleigh: 1 - exp(-x^2 / omega)
+        const N: usize = 100_000;
+        let omega = 2.0;
+        let distr = Nakagami::new(1.0, omega).unwrap();
+        let mut rng = crate::test::rng(232);
+        let points = [0.5, 1.0, 1.5, 2.5];
+        let mut below = [0usize; 4];
+        for _ in 0..N {
+            let x = distr.sample(&mut rng);
+            for (count, &p) in below.iter_mut().zip(points.iter()) {
+                if x < p {
+                    *count += 1;
+                }
+            }
+        }
+        for (&count, &p) in below.iter().zip(points.iter()) {
+            let expected = 1.0 - (-p * p / omega).exp();
+            assert_almost_eq!(count as f64 / N as f64, expected, 0.005);
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(233);
+        let distr = Nakagami::new(2.0, 1.0).unwrap();
+        let expected = [
+            1.2378328827133094,
+            1.6782787533200503,
+            1.3105215431152877,
+            0.753018533103438,
+        ];
+        for &v in expected.iter() {
+            let x: f64 = distr.sample(&mut rng);
+            assert_almost_eq!(x, v, 1e-14);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The noncentral Student t distribution.
+
+use num_traits::Float;
+use crate::{ChiSquared, Distribution, Exp1, Open01, StandardNormal};
+use rand::Rng;
+use core::fmt;
+
+/// The noncentral Student t distribution `t(nu, mu)`, where `nu` is the
+/// degrees of freedom and `mu` the noncentrality parameter.
+///
+/// This is the distribution of `(Z + mu) / sqrt(V / nu)`, where `Z` is a
+/// standard normal and `V` an independent `ChiSquared(nu)` sample. With
+/// `mu == 0` it is the [`StudentT`] distribution; a positive noncentrality
+/// shifts the distribution to the right (and skews it).
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, NoncentralT};
+///
+/// let t = NoncentralT::new(11.0, 1.5).unwrap();
+/// let v = t.sample(&mut rand::thread_rng());
+/// println!("{} is from a noncentral t(11, 1.5) distribution", v)
+/// ```
+///
+/// [`StudentT`]: crate::StudentT
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoncentralT<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+    Exp1: Distribution<F>,
+    Open01: Distribution<F>,
+{
+    chi: ChiSquared<F>,
+    dof: F,
+    noncentrality: F,
+}
+
+/// Error type returned from `NoncentralT::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `nu <= 0` or `nan`.
+    DoFTooSmall,
+    /// `mu` is not finite.
+    NoncentralityNotFinite,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::DoFTooSmall => {
+                "degrees-of-freedom nu is not positive in noncentral t distribution"
+            }
+            Error::NoncentralityNotFinite => {
+                "noncentrality mu is not finite in noncentral t distribution"
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> NoncentralT<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+    Exp1: Distribution<F>,
+    Open01: Distribution<F>,
+{
+    /// Create a new noncentral t distribution with `dof` degrees of freedom
+    /// and the given `noncentrality`.
+    pub fn new(dof: F, noncentrality: F) -> Result<NoncentralT<F>, Error> {
+        if !(dof > F::zero()) {
+            return Err(Error::DoFTooSmall);
+        }
+        if !noncentrality.is_finite() {
+            return Err(Error::NoncentralityNotFinite);
+        }
+        Ok(NoncentralT {
+            chi: ChiSquared::new(dof).map_err(|_| Error::DoFTooSmall)?,
+            dof,
+            noncentrality,
+        })
+    }
+}
+
+impl<F> Distribution<F> for NoncentralT<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+    Exp1: Distribution<F>,
+    Open01: Distribution<F>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let norm: F = rng.sample(StandardNormal);
+        (norm + self.noncentrality) * (self.dof / self.chi.sample(rng)).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::StudentT;
+
+    #[test]
+    fn test_noncentral_t_invalid() {
+        assert_eq!(NoncentralT::new(0.0, 1.0).unwrap_err(), Error::DoFTooSmall);
+        assert_eq!(NoncentralT::new(core::f64::NAN, 1.0).unwrap_err(), Error::DoFTooSmall);
+        assert_eq!(
+            NoncentralT::new(1.0, core::f64::INFINITY).unwrap_err(),
+            Error::NoncentralityNotFinite
+        );
+    }
+
+    #[test]
+    fn test_noncentral_t_central() {
+        let t = StudentT::new(5.0).unwrap();
+        let nct = NoncentralT::new(5.0, 0.0).unwrap();
+        let mut rng1 = crate::test::rng(1720);
+        let mut rng2 = crate::test::rng(1720);
+        for _ in 0..100 {
+            let x: f64 = t.sample(&mut rng1);
+            assert_eq!(nct.sample(&mut rng2), x);
+        }
+    }
+
+    #[test]
+    fn test_noncentral_t_mean() {
+        const N: usize = 100_000;
+        let mut rng = crate::test::rng(1721);
+        let nct = NoncentralT::new(10.0, 2.0).unwrap();
+        let mean = (0..N).map(|_| nct.sample(&mut rng)).sum::<f64>() / N as f64;
+        // mu * sqrt(nu / 2) * Gamma((nu - 1) / 2) / Gamma(nu / 2)
+        assert_almost_eq!(mean, 2.1675, 0.02);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(219);
+        let distr = NoncentralT::new(3.0, 1.5).unwrap();
+        let expected = [
+            5.864629228531382,
+            0.8477508415733833,
+            1.6057869390837063,
+            2.905075864502734,
+        ];
+        for &v in expected.iter() {
+            let x: f64 = distr.sample(&mut rng);
+            assert_almost_eq!(x, v, 1e-14);
+        }
+    }
+}
@@ -124,7 +124,8 @@ where F: Float, StandardNormal: Distribution<F>
 /// Error type returned from `Normal::new` and `LogNormal::new`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Error {
-    /// The mean value is too small (log-normal samples must be positive)
+    /// The mean or median value is too small (log-normal samples must be
+    /// positive)
     MeanTooSmall,
     /// The standard deviation or other dispersion parameter is not finite.
     BadVariance,
@@ -288,6 +289,37 @@ where F: Float, StandardNormal: Distribution<F>
         Ok(LogNormal { norm })
     }
 
+    /// Construct, from (linear-space) median and log-space standard deviation
+    ///
+    /// The median of a log-normal distribution is `exp(μ)`, hence this is
+    /// equivalent to `LogNormal::new(median.ln(), sigma)`:
+    ///
+    /// -   `median` (`m > 0`) is the (real) median of the distribution
+    /// -   `sigma` (`σ ≥ 0`, must be finite) is the standard deviation of the
+    ///     underlying Normal distribution
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand_distr::{Distribution, LogNormal};
+    ///
+    /// // Incomes with a median of 40000 and log-space deviation 0.5
+    /// let incomes = LogNormal::from_median(40000.0, 0.5).unwrap();
+    /// let v = incomes.sample(&mut rand::thread_rng());
+    /// println!("{}", v);
+    /// ```
+    #[inline]
+    pub fn from_median(median: F, sigma: F) -> Result<LogNormal<F>, Error> {
+        if !(median > F::zero()) {
+            return Err(Error::MeanTooSmall);
+        }
+        if !(sigma >= F::zero()) {
+            return Err(Error::BadVariance);
+        }
+        let norm = Normal::new(median.ln(), sigma)?;
+        Ok(LogNormal { norm })
+    }
+
     /// Sample from a z-score
     ///
     /// This may be useful for generating correlated samples `x1` and `x2`
@@ -363,6 +395,29 @@ mod tests {
         assert_eq!(lnorm.norm.std_dev, 1.0);
     }
     #[test]
+    fn test_log_normal_from_median() {
+        let lnorm = LogNormal::from_median(1.0, 2.0).unwrap();
+        assert_eq!((lnorm.norm.mean, lnorm.norm.std_dev), (0.0, 2.0));
+
+        let mut rng = crate::test::rng(1697);
+        for &(median, sigma) in &[(5.0, 0.5), (0.01, 2.0), (1000.0, 1.0)] {
+            let lnorm = LogNormal::from_median(median, sigma).unwrap();
+            let mut samples: [f64; 10001] = [0.0; 10001];
+            for x in samples.iter_mut() {
+                *x = lnorm.sample(&mut rng);
+            }
+            samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let empirical = samples[samples.len() / 2];
+            assert!((empirical / median - 1.0).abs() < 0.05 * (1.0 + sigma));
+        }
+
+        assert_eq!(LogNormal::from_median(0.0, 1.0).unwrap_err(), Error::MeanTooSmall);
+        assert_eq!(LogNormal::from_median(-1.0, 1.0).unwrap_err(), Error::MeanTooSmall);
+        assert_eq!(LogNormal::from_median(core::f64::NAN, 1.0).unwrap_err(), Error::MeanTooSmall);
+        assert_eq!(LogNormal::from_median(1.0, -1.0).unwrap_err(), Error::BadVariance);
+        assert_eq!(LogNormal::from_median(1.0, core::f64::INFINITY).unwrap_err(), Error::BadVariance);
+    }
+    #[test]
     fn test_log_normal_invalid_sd() {
         assert!(LogNormal::from_mean_cv(-1.0, 1.0).is_err());
         assert!(LogNormal::from_mean_cv(0.0, 1.0).is_err());
@@ -0,0 +1,221 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A distribution with a piecewise-linear CDF.
+
+use num_traits::Float;
+use crate::{Distribution, Standard};
+use rand::Rng;
+use core::fmt;
+use alloc::vec::Vec;
+
+/// A continuous distribution whose cumulative distribution function (CDF)
+/// is piecewise linear between the given control points.
+///
+/// Each control point `(x, p)` states that a sample is less than `x` with
+/// probability `p`. The density is constant between consecutive points,
+/// hence this is a histogram-like distribution, useful for sampling from
+/// empirical data.
+///
+/// Sampling draws `u` from [`Standard`], finds the segment of the CDF
+/// containing `u` by binary search, and linearly interpolates the inverse
+/// CDF within that segment.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_distr::PiecewiseLinear;
+///
+/// // Half of the samples lie in [0, 1), the other half in [1, 10)
+/// let d = PiecewiseLinear::new(&[(0.0, 0.0), (1.0, 0.5), (10.0, 1.0)]).unwrap();
+/// let v = d.sample(&mut rand::thread_rng());
+/// println!("{} is from a piecewise-linear distribution", v);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct PiecewiseLinear<F>
+where F: Float, Standard: Distribution<F>
+{
+    xs: Vec<F>,
+    ps: Vec<F>,
+}
+
+/// Error type returned from `PiecewiseLinear::new`.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Fewer than two control points.
+    TooFewPoints,
+    /// An `x` coordinate is not finite or is less than the previous one.
+    InvalidX,
+    /// The probabilities are not non-decreasing from exactly 0 to exactly 1.
+    InvalidProbability,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::TooFewPoints => "fewer than two points in piecewise-linear distribution",
+            Error::InvalidX => "x is not finite and non-decreasing in piecewise-linear distribution",
+            Error::InvalidProbability => {
+                "probabilities do not increase from 0 to 1 in piecewise-linear distribution"
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> PiecewiseLinear<F>
+where F: Float, Standard: Distribution<F>
+{
+    /// Construct a new `PiecewiseLinear` from `(x, cumulative_probability)`
+    /// control points.
+    ///
+    /// The `x` coordinates must be finite and non-decreasing. The
+    /// probabilities must be non-decreasing, starting at exactly 0 and ending
+    /// at exactly 1.
+    pub fn new(points: &[(F, F)]) -> Result<PiecewiseLinear<F>, Error> {
+        if points.len() < 2 {
+            return Err(Error::TooFewPoints);
+        }
+        if points[0].1 != F::zero() || points[points.len() - 1].1 != F::one() {
+            return Err(Error::InvalidProbability);
+        }
+        let mut xs = Vec::with_capacity(points.len());
+        let mut ps = Vec::with_capacity(points.len());
+        for (i, &(x, p)) in points.iter().enumerate() {
+            if !x.is_finite() || (i > 0 && !(x >= xs[i - 1])) {
+                return Err(Error::InvalidX);
+            }
+            if i > 0 && !(p >= ps[i - 1]) {
+                return Err(Error::InvalidProbability);
+            }
+            xs.push(x);
+            ps.push(p);
+        }
+        Ok(PiecewiseLinear { xs, ps })
+    }
+}
+
+impl<F> Distribution<F> for PiecewiseLinear<F>
+where F: Float, Standard: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(Standard);
+        // Find the first point with `p > u`; since `ps[0] == 0 <= u < 1`
+        // and the last probability is 1, this is in `1..len`.
+        let i = match self.ps.binary_search_by(|&p| {
+            if p <= u {
+                core::cmp::Ordering::Less
+            } else {
+                core::cmp::Ordering::Greater
+            }
+        }) {
+            Ok(i) | Err(i) => i,
+        };
+        let (x0, x1) = (self.xs[i - 1], self.xs[i]);
+        let (p0, p1) = (self.ps[i - 1], self.ps[i]);
+        x0 + (x1 - x0) * ((u - p0) / (p1 - p0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_piecewise_linear_invalid() {
+        assert_eq!(PiecewiseLinear::new(&[(0.0, 0.0)]).unwrap_err(), Error::TooFewPoints);
+        assert_eq!(
+            PiecewiseLinear::new(&[(0.0, 0.1), (1.0, 1.0)]).unwrap_err(),
+            Error::InvalidProbability
+        );
+        assert_eq!(
+            PiecewiseLinear::new(&[(0.0, 0.0), (1.0, 0.9)]).unwrap_err(),
+            Error::InvalidProbability
+        );
+        assert_eq!(
+            PiecewiseLinear::new(&[(0.0, 0.0), (1.0, 0.6), (2.0, 0.4), (3.0, 1.0)]).unwrap_err(),
+            Error::InvalidProbability
+        );
+        assert_eq!(
+            PiecewiseLinear::new(&[(1.0, 0.0), (0.0, 1.0)]).unwrap_err(),
+            Error::InvalidX
+        );
+        assert_eq!(
+            PiecewiseLinear::new(&[(0.0, 0.0), (core::f64::INFINITY, 1.0)]).unwrap_err(),
+            Error::InvalidX
+        );
+    }
+
+    #[test]
+    fn test_piecewise_linear_uniform() {
+        const N: usize = 100_000;
+        let mut rng = crate::test::rng(1722);
+        let d = PiecewiseLinear::new(&[(2.0, 0.0), (5.0, 1.0)]).unwrap();
+        let mut sum = 0.0;
+        let mut counts = [0usize; 3];
+        for _ in 0..N {
+            let x = d.sample(&mut rng);
+            assert!((2.0..5.0).contains(&x));
+            counts[(x - 2.0) as usize] += 1;
+            sum += x;
+        }
+        assert_almost_eq!(sum / N as f64, 3.5, 0.01);
+        for &c in &counts {
+            assert_almost_eq!(c as f64 / N as f64, 1.0 / 3.0, 0.01);
+        }
+    }
+
+    #[test]
+    fn test_piecewise_linear_segments() {
+        const N: usize = 100_000;
+        let mut rng = crate::test::rng(1723);
+        // A flat segment (zero probability) and a vertical one (an atom)
+        let d = PiecewiseLinear::new(&[
+            (0.0, 0.0),
+            (1.0, 0.5),
+            (4.0, 0.5),
+            (4.0, 0.75),
+            (10.0, 1.0),
+        ])
+        .unwrap();
+        let mut below_one = 0;
+        let mut at_four = 0;
+        for _ in 0..N {
+            let x = d.sample(&mut rng);
+            assert!((0.0..10.0).contains(&x));
+            assert!(!(1.0..4.0).contains(&x));
+            below_one += (x < 1.0) as usize;
+            at_four += (x == 4.0) as usize;
+        }
+        assert_almost_eq!(below_one as f64 / N as f64, 0.5, 0.01);
+        assert_almost_eq!(at_four as f64 / N as f64, 0.25, 0.01);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(220);
+        let d = PiecewiseLinear::new(&[(0.0, 0.0), (1.0, 0.5), (10.0, 1.0)]).unwrap();
+        let expected = [
+            0.4643174349342951,
+            0.7030902688127718,
+            0.8918148947305111,
+            2.6439049660974994,
+        ];
+        for &v in expected.iter() {
+            let x: f64 = d.sample(&mut rng);
+            assert_almost_eq!(x, v, 1e-14);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A stateful sampler of booleans with a given expected run length.
+
+use crate::{Bernoulli, Distribution};
+use rand::Rng;
+use core::fmt;
+
+/// A stateful sampler of booleans, yielding runs of identical values with a
+/// given expected length.
+///
+/// Each call after the first flips the previous value with probability
+/// `1 / expected_run`, hence the lengths of runs of identical values follow
+/// a geometric distribution on `1, 2, ...` with mean `expected_run`. The
+/// first value is `true` or `false` with equal probability. This is useful
+/// to simulate bursty binary signals.
+///
+/// Since the result depends on previous calls, this is not a
+/// [`Distribution`].
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::RunLengthBool;
+///
+/// let mut rng = rand::thread_rng();
+/// // A link which is up or down for 20 ticks on average
+/// let mut link = RunLengthBool::new(20.0).unwrap();
+/// let up: Vec<bool> = (0..100).map(|_| link.next(&mut rng)).collect();
+/// println!("{:?}", up);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunLengthBool {
+    flip: Bernoulli,
+    current: Option<bool>,
+}
+
+/// Error type returned from `RunLengthBool::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `expected_run < 1` or `nan`.
+    RunTooShort,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::RunTooShort => "expected_run < 1 or is NaN in run-length sampler",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl RunLengthBool {
+    /// Construct a new `RunLengthBool` with runs of mean length
+    /// `expected_run`.
+    ///
+    /// An `expected_run` of 1 yields alternating values, while an infinite
+    /// `expected_run` repeats the first value forever.
+    pub fn new(expected_run: f64) -> Result<RunLengthBool, Error> {
+        if !(expected_run >= 1.0) {
+            return Err(Error::RunTooShort);
+        }
+        let flip = Bernoulli::new(1.0 / expected_run).map_err(|_| Error::RunTooShort)?;
+        Ok(RunLengthBool { flip, current: None })
+    }
+
+    /// Sample the next boolean.
+    pub fn next<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
+        let value = match self.current {
+            None => rng.gen(),
+            Some(previous) => previous ^ self.flip.sample(rng),
+        };
+        self.current = Some(value);
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_run_length_bool_invalid() {
+        assert_eq!(RunLengthBool::new(0.5).unwrap_err(), Error::RunTooShort);
+        assert_eq!(RunLengthBool::new(-1.0).unwrap_err(), Error::RunTooShort);
+        assert_eq!(RunLengthBool::new(core::f64::NAN).unwrap_err(), Error::RunTooShort);
+    }
+
+    #[test]
+    fn test_run_length_bool_extremes() {
+        let mut rng = crate::test::rng(1704);
+        let mut d = RunLengthBool::new(1.0).unwrap();
+        let first = d.next(&mut rng);
+        for i in 1..100 {
+            assert_eq!(d.next(&mut rng), first ^ (i % 2 == 1));
+        }
+        let mut d = RunLengthBool::new(core::f64::INFINITY).unwrap();
+        let first = d.next(&mut rng);
+        assert!((0..100).all(|_| d.next(&mut rng) == first));
+    }
+
+    #[test]
+    fn test_run_length_bool_mean() {
+        const N: usize = 100_000;
+        let mut rng = crate::test::rng(1705);
+        for &expected_run in &[1.5, 4.0, 25.0] {
+            let mut d = RunLengthBool::new(expected_run).unwrap();
+            let mut previous = d.next(&mut rng);
+            let mut runs = 1;
+            let mut trues = previous as usize;
+            for _ in 1..N {
+                let x = d.next(&mut rng);
+                if x != previous {
+                    runs += 1;
+                }
+                trues += x as usize;
+                previous = x;
+            }
+            let mean = N as f64 / runs as f64;
+            assert!((mean / expected_run - 1.0).abs() < 0.05);
+            // Both values should be equally common in the long run
+            assert!((trues as f64 / N as f64 - 0.5).abs() < 0.05);
+        }
+    }
+}
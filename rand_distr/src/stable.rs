@@ -0,0 +1,208 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Lévy alpha-stable distribution.
+
+use num_traits::{Float, FloatConst};
+use crate::{Distribution, Exp1, Open01};
+use rand::Rng;
+use core::fmt;
+
+/// The Lévy alpha-stable distribution `Stable(α, β)` with stability `α` and
+/// skewness `β`, unit scale and zero location.
+///
+/// Stable distributions generalize the normal distribution to heavy tails:
+/// for `α < 2` the tails decay like `|x|^-α`, so the variance is infinite
+/// (and for `α <= 1` also the mean). Special cases are `α = 2`, a normal
+/// distribution with variance 2, and `α = 1, β = 0`, the standard
+/// [`Cauchy`](crate::Cauchy) distribution. A positive `β` skews the
+/// distribution to the right. The parametrization is `S1` in the notation of
+/// Nolan; to scale or shift samples, multiply or add to them.
+///
+/// Samples are generated with the Chambers-Mallows-Stuck method from a
+/// uniformly distributed angle and an exponential sample.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Stable};
+///
+/// let returns = Stable::new(1.7, 0.0).unwrap();
+/// let v = returns.sample(&mut rand::thread_rng());
+/// println!("{}", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stable<F>
+where F: Float + FloatConst, Exp1: Distribution<F>, Open01: Distribution<F>
+{
+    alpha: F,
+    beta: F,
+    // For `alpha != 1`: the shift of the angle and the scale factor
+    b: F,
+    s: F,
+}
+
+/// Error type returned from `Stable::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `alpha` is not in the range `(0, 2]`.
+    AlphaInvalid,
+    /// `|beta| > 1` or `beta` is `nan`.
+    BetaInvalid,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::AlphaInvalid => "alpha is not in (0, 2] in stable distribution",
+            Error::BetaInvalid => "beta is not in [-1, 1] in stable distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> Stable<F>
+where F: Float + FloatConst, Exp1: Distribution<F>, Open01: Distribution<F>
+{
+    /// Construct a new `Stable` distribution with stability `alpha` and
+    /// skewness `beta`.
+    pub fn new(alpha: F, beta: F) -> Result<Stable<F>, Error> {
+        let one = F::one();
+        if !(alpha > F::zero() && alpha <= one + one) {
+            return Err(Error::AlphaInvalid);
+        }
+        if !(beta.abs() <= one) {
+            return Err(Error::BetaInvalid);
+        }
+        let (b, s) = if alpha == one {
+            (F::zero(), one)
+        } else {
+            let zeta = beta * (alpha * F::FRAC_PI_2()).tan();
+            (zeta.atan() / alpha, (one + zeta * zeta).powf(one / (alpha + alpha)))
+        };
+        Ok(Stable { alpha, beta, b, s })
+    }
+}
+
+impl<F> Distribution<F> for Stable<F>
+where F: Float + FloatConst, Exp1: Distribution<F>, Open01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let one = F::one();
+        let half_pi = F::FRAC_PI_2();
+        let u: F = rng.sample(Open01);
+        let v = F::PI() * (u - F::from(0.5).unwrap());
+        let w: F = rng.sample(Exp1);
+
+        if self.alpha == one {
+            let t = half_pi + self.beta * v;
+            (t * v.tan() - self.beta * (half_pi * w * v.cos() / t).ln()) / half_pi
+        } else {
+            let a = self.alpha * (v + self.b);
+            self.s * a.sin() / v.cos().powf(one / self.alpha)
+                * ((v - a).cos() / w).powf((one - self.alpha) / self.alpha)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Fraction of `n` samples of `distr` which are below each of `points`
+    fn cdf<D: Distribution<f64>>(distr: &D, seed: u64, n: usize, points: &[f64; 3]) -> [f64; 3] {
+        let mut rng = crate::test::rng(seed);
+        let mut below = [0usize; 3];
+        for _ in 0..n {
+            let x = distr.sample(&mut rng);
+            for (count, &p) in below.iter_mut().zip(points.iter()) {
+                if x < p {
+                    *count += 1;
+                }
+            }
+        }
+        let mut result = [0.0; 3];
+        for (r, &count) in result.iter_mut().zip(below.iter()) {
+            *r = count as f64 / n as f64;
+        }
+        result
+    }
+
+    #[test]
+    fn test_stable_invalid() {
+        assert_eq!(Stable::new(0.0, 0.0).unwrap_err(), Error::AlphaInvalid);
+        assert_eq!(Stable::new(2.1, 0.0).unwrap_err(), Error::AlphaInvalid);
+        assert_eq!(Stable::new(core::f64::NAN, 0.0).unwrap_err(), Error::AlphaInvalid);
+        assert_eq!(Stable::new(1.5, 1.1).unwrap_err(), Error::BetaInvalid);
+        assert_eq!(Stable::new(1.5, core::f64::NAN).unwrap_err(), Error::BetaInvalid);
+        assert!(Stable::new(2.0, -1.0).is_ok());
+    }
+
+    #[test]
+    fn test_stable_special_cases() {
+        const N: usize = 100_000;
+        // alpha = 2 is Normal(0, sqrt(2)): Phi(x / sqrt(2))
+        let distr = Stable::new(2.0, 0.0).unwrap();
+        let result = cdf(&distr, 234, N, &[-0.5, 1.0, 2.0]);
+        let expected = [0.3618368, 0.7602499, 0.9213504];
+        for (&r, &e) in result.iter().zip(expected.iter()) {
+            assert_almost_eq!(r, e, 0.005);
+        }
+
+        // alpha = 1, beta = 0 is the standard Cauchy distribution
+        let distr = Stable::new(1.0, 0.0).unwrap();
+        let result = cdf(&distr, 235, N, &[-1.0, 0.0, 3.0]);
+        let expected = [0.25, 0.5, 0.5 + 3f64.atan() / core::f64::consts::PI];
+        for (&r, &e) in result.iter().zip(expected.iter()) {
+            assert_almost_eq!(r, e, 0.005);
+        }
+    }
+
+    #[test]
+    fn test_stable_tails() {
+        // Smaller alpha yields heavier tails
+        const N: usize = 100_000;
+        let mut previous = 0.0;
+        for &alpha in [2.0, 1.8, 1.5, 1.0, 0.7].iter() {
+            let distr = Stable::new(alpha, 0.0).unwrap();
+            let result = cdf(&distr, 236, N, &[-5.0, 0.0, 5.0]);
+            let tail = result[0] + 1.0 - result[2];
+            assert!(tail > previous, "alpha = {}: {}", alpha, tail);
+            // Symmetric for beta = 0
+            assert_almost_eq!(result[1], 0.5, 0.005);
+            previous = tail;
+        }
+
+        // Positive beta skews to the right
+        let distr = Stable::new(1.5, 1.0).unwrap();
+        let result = cdf(&distr, 237, N, &[-5.0, 0.0, 5.0]);
+        assert!(1.0 - result[2] > 2.0 * result[0]);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(238);
+        let expected = [
+            0.7483513048740993,
+            1.1849660655034844,
+            -2.155288746547698,
+            -0.0515266006107197,
+        ];
+        for (i, &(alpha, beta)) in [(1.5, 0.5), (1.0, -0.3)].iter().enumerate() {
+            let distr = Stable::new(alpha, beta).unwrap();
+            for &v in expected[2 * i..2 * i + 2].iter() {
+                let x: f64 = distr.sample(&mut rng);
+                assert_almost_eq!(x, v, 1e-14);
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The symmetric triangular integer distribution.
+
+use crate::{Distribution, Uniform};
+use rand::Rng;
+use core::fmt;
+
+/// The symmetric triangular distribution over the integers
+/// `[-half_width, half_width]`.
+///
+/// Samples are the difference of two independent uniform samples from
+/// `[0, half_width]`, like the difference of two dice rolls. The result is
+/// bell-shaped with mode zero: the probability of `k` is proportional to
+/// `half_width + 1 - |k|`.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, SymTriangularInt};
+///
+/// let d = SymTriangularInt::new(5).unwrap();
+/// let v = d.sample(&mut rand::thread_rng());
+/// assert!(-5 <= v && v <= 5);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymTriangularInt {
+    uniform: Uniform<u32>,
+}
+
+/// Error type returned from `SymTriangularInt::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `half_width > i32::MAX`.
+    HalfWidthTooLarge,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::HalfWidthTooLarge => {
+                "half_width is too large in symmetric triangular integer distribution"
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl SymTriangularInt {
+    /// Construct a new `SymTriangularInt` sampling from
+    /// `[-half_width, half_width]`.
+    pub fn new(half_width: u32) -> Result<SymTriangularInt, Error> {
+        if half_width > ::core::i32::MAX as u32 {
+            return Err(Error::HalfWidthTooLarge);
+        }
+        Ok(SymTriangularInt {
+            uniform: Uniform::new_inclusive(0, half_width),
+        })
+    }
+}
+
+impl Distribution<i32> for SymTriangularInt {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> i32 {
+        let a = self.uniform.sample(rng) as i32;
+        let b = self.uniform.sample(rng) as i32;
+        a - b
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sym_triangular_int_invalid() {
+        assert_eq!(
+            SymTriangularInt::new(::core::i32::MAX as u32 + 1).unwrap_err(),
+            Error::HalfWidthTooLarge
+        );
+        assert!(SymTriangularInt::new(::core::i32::MAX as u32).is_ok());
+    }
+
+    #[test]
+    fn test_sym_triangular_int_zero() {
+        let d = SymTriangularInt::new(0).unwrap();
+        let mut rng = crate::test::rng(1684);
+        for _ in 0..10 {
+            assert_eq!(d.sample(&mut rng), 0);
+        }
+    }
+
+    #[test]
+    fn test_sym_triangular_int_shape() {
+        const N: u32 = 90_000;
+        let d = SymTriangularInt::new(2).unwrap();
+        let mut rng = crate::test::rng(1685);
+        let mut counts = [0u32; 5];
+        for _ in 0..N {
+            let v = d.sample(&mut rng);
+            assert!((-2..=2).contains(&v));
+            counts[(v + 2) as usize] += 1;
+        }
+
+        // P(k) = (3 - |k|) / 9
+        for (i, &expected) in [1, 2, 3, 2, 1].iter().enumerate() {
+            let expected = (expected * N / 9) as f64;
+            assert!((counts[i] as f64 - expected).abs() < 0.05 * expected);
+        }
+        // Zero is the mode
+        assert!(counts.iter().all(|&c| c <= counts[2]));
+        // The distribution is symmetric
+        for i in 0..2 {
+            let (lo, hi) = (counts[i] as f64, counts[4 - i] as f64);
+            assert!((lo - hi).abs() < 0.05 * lo);
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let d = SymTriangularInt::new(100).unwrap();
+        let mut rng = crate::test::rng(215);
+        let mut buf = [0i32; 6];
+        for x in buf.iter_mut() {
+            *x = d.sample(&mut rng);
+        }
+        assert_eq!(buf, [71, -8, -9, -7, 36, -30]);
+    }
+}
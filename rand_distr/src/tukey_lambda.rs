@@ -0,0 +1,176 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Tukey lambda distribution.
+
+use num_traits::Float;
+use crate::{Distribution, Open01};
+use rand::Rng;
+use core::fmt;
+
+/// The Tukey lambda distribution `TukeyLambda(lambda)`.
+///
+/// This is a family of distributions symmetric about zero, defined by its
+/// quantile function `Q(u) = (u^lambda - (1 - u)^lambda) / lambda`, and
+/// `Q(u) = ln(u / (1 - u))` in the limit `lambda = 0`. The shape parameter
+/// `lambda` controls the tails:
+///
+/// - `lambda < 0`: heavy tails, like the Student's t distribution
+/// - `lambda = 0`: the logistic distribution
+/// - `lambda ≈ 0.14`: approximately the normal distribution
+/// - `lambda = 1`: the uniform distribution on `[-1, 1]`
+/// - `lambda > 0`: bounded support on `[-1 / lambda, 1 / lambda]`
+///
+/// Samples are generated via the quantile function, taking `u` from
+/// [`Open01`] so that samples are finite for `lambda >= 0`. For large
+/// negative `lambda`, `u^lambda` overflows for `u` close to 0 or 1, so
+/// samples may be infinite (e.g. for `lambda < -19` with `f64` or
+/// `lambda < -5` with `f32`).
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, TukeyLambda};
+///
+/// let tl = TukeyLambda::new(0.14).unwrap();
+/// let v: f64 = tl.sample(&mut rand::thread_rng());
+/// println!("{} is from a TukeyLambda(0.14) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct TukeyLambda<F>
+where F: Float, Open01: Distribution<F>
+{
+    lambda: F,
+}
+
+/// Error type returned from `TukeyLambda::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `lambda` is infinite or `nan`.
+    LambdaNotFinite,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::LambdaNotFinite => "lambda is not finite in Tukey lambda distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<F> TukeyLambda<F>
+where F: Float, Open01: Distribution<F>
+{
+    /// Construct a new `TukeyLambda` distribution with shape `lambda`.
+    pub fn new(lambda: F) -> Result<TukeyLambda<F>, Error> {
+        if !lambda.is_finite() {
+            return Err(Error::LambdaNotFinite);
+        }
+        Ok(TukeyLambda { lambda })
+    }
+}
+
+impl<F> Distribution<F> for TukeyLambda<F>
+where F: Float, Open01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(Open01);
+        if self.lambda == F::zero() {
+            // ln(u / (1 - u))
+            u.ln() - (-u).ln_1p()
+        } else {
+            (u.powf(self.lambda) - (F::one() - u).powf(self.lambda)) / self.lambda
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tukey_lambda_invalid() {
+        assert_eq!(TukeyLambda::new(core::f64::NAN).unwrap_err(), Error::LambdaNotFinite);
+        assert_eq!(TukeyLambda::new(core::f64::INFINITY).unwrap_err(), Error::LambdaNotFinite);
+        assert!(TukeyLambda::new(-3.0).is_ok());
+    }
+
+    #[test]
+    fn test_tukey_lambda_logistic() {
+        const N: usize = 100_000;
+        let distr = TukeyLambda::new(0.0).unwrap();
+        let mut rng = crate::test::rng(250);
+        let points = [-3.0, -1.0, -0.25, 0.0, 0.5, 2.0];
+        let mut below = [0usize; 6];
+        for _ in 0..N {
+            let x: f64 = distr.sample(&mut rng);
+            for (p, b) in points.iter().zip(below.iter_mut()) {
+                if x <= *p {
+                    *b += 1;
+                }
+            }
+        }
+        // Compare with the CDF of the standard logistic distribution
+        for (p, &b) in points.iter().zip(below.iter()) {
+            let cdf = 1.0 / (1.0 + (-p).exp());
+            assert_almost_eq!(b as f64 / N as f64, cdf, 0.005);
+        }
+    }
+
+    #[test]
+    fn test_tukey_lambda_symmetric() {
+        const N: usize = 20_000;
+        let mut rng = crate::test::rng(251);
+        for &lambda in &[-0.5, 0.0, 0.14, 1.0, 3.0] {
+            let distr = TukeyLambda::new(lambda).unwrap();
+            let mut samples = [0.0f64; N];
+            for x in samples.iter_mut() {
+                *x = distr.sample(&mut rng);
+                assert!(x.is_finite());
+                if lambda > 0.0 {
+                    assert!(x.abs() <= 1.0 / lambda);
+                }
+            }
+            let negative = samples.iter().filter(|&&x| x < 0.0).count();
+            assert_almost_eq!(negative as f64 / N as f64, 0.5, 0.015);
+
+            // The quantiles at `p` and `1 - p` should be opposite
+            samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for &i in &[N / 10, N / 4] {
+                let (lo, hi) = (samples[i], samples[N - 1 - i]);
+                assert!((lo + hi).abs() < 0.05 * (hi - lo), "lambda = {}: {} vs {}", lambda, lo, hi);
+            }
+        }
+
+        // With `lambda = 1`, this is the uniform distribution on `[-1, 1]`
+        let distr = TukeyLambda::new(1.0).unwrap();
+        let mean = (0..N).map(|_| distr.sample(&mut rng)).sum::<f64>() / N as f64;
+        assert_almost_eq!(mean, 0.0, 0.02);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(252);
+        let distr = TukeyLambda::new(0.14).unwrap();
+        let expected = [
+            -1.0266734590405429,
+            -0.7659937428580945,
+            -0.4251439868651975,
+            -3.6683708032176856,
+        ];
+        for &v in expected.iter() {
+            let x: f64 = distr.sample(&mut rng);
+            assert_almost_eq!(x, v, 1e-14);
+        }
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uniform sampling of characters from a range of code points.
+
+use crate::{Distribution, Uniform};
+use rand::Rng;
+use core::fmt;
+
+// Surrogate code points, which are not valid `char`s
+const SURROGATE_START: u32 = 0xD800;
+const SURROGATE_END: u32 = 0xDFFF;
+
+/// Samples `char`s uniformly from a range of code points such as a Unicode
+/// block, for example to test internationalization.
+///
+/// All Unicode scalar values in the inclusive range `[start, end]` are
+/// equally likely; surrogate code points (`U+D800` to `U+DFFF`) are
+/// skipped. This does not consult the Unicode character database, hence
+/// unassigned code points within the range may be returned; use precise
+/// ranges to avoid them.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, UnicodeBlock};
+///
+/// // The Emoticons block
+/// let emoticons = UnicodeBlock::new(0x1F600, 0x1F64F).unwrap();
+/// let c = emoticons.sample(&mut rand::thread_rng());
+/// assert!(('\u{1F600}'..='\u{1F64F}').contains(&c));
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnicodeBlock {
+    sampler: Uniform<u32>,
+    // Sampled values from `gap_start` onwards are shifted past the
+    // surrogates by `gap_len`
+    gap_start: u32,
+    gap_len: u32,
+}
+
+/// Error type returned from `UnicodeBlock::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `start > end`.
+    EmptyRange,
+    /// `end` is greater than `char::MAX`.
+    NotScalarValue,
+    /// The range consists only of surrogate code points.
+    OnlySurrogates,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::EmptyRange => "start > end in Unicode block",
+            Error::NotScalarValue => "end is not a Unicode scalar value in Unicode block",
+            Error::OnlySurrogates => "range consists only of surrogates in Unicode block",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl UnicodeBlock {
+    /// Construct a new `UnicodeBlock` sampling from the code points in the
+    /// inclusive range `[start, end]`.
+    pub fn new(start: u32, end: u32) -> Result<UnicodeBlock, Error> {
+        if start > end {
+            return Err(Error::EmptyRange);
+        }
+        if end > core::char::MAX as u32 {
+            return Err(Error::NotScalarValue);
+        }
+        let gap_start = start.max(SURROGATE_START);
+        let gap_end = end.min(SURROGATE_END);
+        let gap_len = if gap_start <= gap_end {
+            gap_end - gap_start + 1
+        } else {
+            0
+        };
+        if gap_len == end - start + 1 {
+            return Err(Error::OnlySurrogates);
+        }
+        Ok(UnicodeBlock {
+            sampler: Uniform::new_inclusive(start, end - gap_len),
+            gap_start,
+            gap_len,
+        })
+    }
+}
+
+impl Distribution<char> for UnicodeBlock {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        let mut x = self.sampler.sample(rng);
+        if x >= self.gap_start {
+            x += self.gap_len;
+        }
+        // Ranges are validated on construction and surrogates skipped
+        core::char::from_u32(x).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unicode_block_invalid() {
+        assert_eq!(UnicodeBlock::new(0x100, 0xFF).unwrap_err(), Error::EmptyRange);
+        assert_eq!(UnicodeBlock::new(0, 0x110000).unwrap_err(), Error::NotScalarValue);
+        assert_eq!(UnicodeBlock::new(0xD800, 0xDFFF).unwrap_err(), Error::OnlySurrogates);
+        assert_eq!(UnicodeBlock::new(0xDA00, 0xDB00).unwrap_err(), Error::OnlySurrogates);
+        assert!(UnicodeBlock::new(0x10FFFF, 0x10FFFF).is_ok());
+    }
+
+    #[test]
+    fn test_unicode_block_emoticons() {
+        let mut rng = crate::test::rng(239);
+        let distr = UnicodeBlock::new(0x1F600, 0x1F64F).unwrap();
+        let mut seen = [false; 0x50];
+        for _ in 0..2000 {
+            let c = distr.sample(&mut rng);
+            assert!(('\u{1F600}'..='\u{1F64F}').contains(&c));
+            seen[c as usize - 0x1F600] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn test_unicode_block_surrogates() {
+        let mut rng = crate::test::rng(240);
+        // Only the two ends are valid
+        let distr = UnicodeBlock::new(0xD7FF, 0xE000).unwrap();
+        let mut counts = [0; 2];
+        for _ in 0..1000 {
+            match distr.sample(&mut rng) {
+                '\u{D7FF}' => counts[0] += 1,
+                '\u{E000}' => counts[1] += 1,
+                c => panic!("unexpected {:?}", c),
+            }
+        }
+        assert!(counts[0] > 400 && counts[1] > 400);
+
+        // Ranges starting or ending within the surrogates
+        let distr = UnicodeBlock::new(0xDC00, 0xE001).unwrap();
+        for _ in 0..100 {
+            assert!(('\u{E000}'..='\u{E001}').contains(&distr.sample(&mut rng)));
+        }
+        let distr = UnicodeBlock::new(0xD7FE, 0xDC00).unwrap();
+        for _ in 0..100 {
+            assert!(('\u{D7FE}'..='\u{D7FF}').contains(&distr.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(241);
+        let distr = UnicodeBlock::new(0x0400, 0x04FF).unwrap();
+        let expected = ['\u{43D}', '\u{44B}', '\u{484}', '\u{4D3}'];
+        for &v in expected.iter() {
+            assert_eq!(distr.sample(&mut rng), v);
+        }
+    }
+}
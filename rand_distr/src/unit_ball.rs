@@ -46,3 +46,26 @@ impl<F: Float + SampleUniform> Distribution<[F; 3]> for UnitBall {
         [x1, x2, x3]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::UnitBall;
+    use crate::Distribution;
+
+    #[test]
+    fn norm_and_radial_distribution() {
+        let mut rng = crate::test::rng(245);
+        const N: u32 = 8000;
+        let mut inner = 0;
+        for _ in 0..N {
+            let x: [f64; 3] = UnitBall.sample(&mut rng);
+            let r2 = x[0] * x[0] + x[1] * x[1] + x[2] * x[2];
+            assert!(r2 <= 1.);
+            // The ball of radius 1/2 holds 1/8 of the volume
+            if r2 <= 0.25 {
+                inner += 1;
+            }
+        }
+        assert_almost_eq!(inner as f64 / N as f64, 0.125, 0.015);
+    }
+}
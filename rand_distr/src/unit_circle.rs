@@ -12,8 +12,12 @@ use rand::Rng;
 
 /// Samples uniformly from the edge of the unit circle in two dimensions.
 ///
-/// Implemented via a method by von Neumann[^1].
+/// Each sample is a unit vector `[x, y]` with `x^2 + y^2 = 1` (up to
+/// rounding), with uniformly distributed angle.
 ///
+/// Implemented via a method by von Neumann[^1], which avoids trigonometric
+/// functions: a point is sampled uniformly in the unit disc by rejection and
+/// its angle doubled.
 ///
 /// # Example
 ///
@@ -43,7 +47,8 @@ impl<F: Float + SampleUniform> Distribution<[F; 2]> for UnitCircle {
             x1 = uniform.sample(rng);
             x2 = uniform.sample(rng);
             sum = x1 * x1 + x2 * x2;
-            if sum < F::from(1.).unwrap() {
+            // Reject the origin, where the angle is undefined
+            if sum < F::from(1.).unwrap() && sum > F::zero() {
                 break;
             }
         }
@@ -65,4 +70,19 @@ mod tests {
             assert_almost_eq!(x[0] * x[0] + x[1] * x[1], 1., 1e-15);
         }
     }
+
+    #[test]
+    fn uniform_angle() {
+        const N: usize = 40_000;
+        let mut rng = crate::test::rng(253);
+        let mut octants = [0usize; 8];
+        for _ in 0..N {
+            let [x, y]: [f64; 2] = UnitCircle.sample(&mut rng);
+            let octant = (x < 0.) as usize * 4 + (y < 0.) as usize * 2 + (x.abs() < y.abs()) as usize;
+            octants[octant] += 1;
+        }
+        for &count in octants.iter() {
+            assert_almost_eq!(count as f64 / N as f64, 0.125, 0.01);
+        }
+    }
 }
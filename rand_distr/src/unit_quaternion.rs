@@ -0,0 +1,146 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use num_traits::{Float, FloatConst};
+use crate::{Distribution, Standard};
+use rand::Rng;
+
+/// Samples uniformly from the unit quaternions, i.e. the surface of the unit
+/// sphere in four dimensions, representing uniformly random rotations in
+/// three dimensions.
+///
+/// Quaternions are returned as `[w, x, y, z]`, with the scalar part `w`
+/// first. Since `q` and `-q` represent the same rotation, both signs of `w`
+/// occur. Use [`UnitQuaternion::to_rotation_matrix`] to obtain the rotation
+/// as a matrix.
+///
+/// Implemented via a method by Shoemake[^1], using three uniform samples.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{UnitQuaternion, Distribution};
+///
+/// let q: [f64; 4] = UnitQuaternion.sample(&mut rand::thread_rng());
+/// let m = UnitQuaternion::to_rotation_matrix(q);
+/// println!("{:?} is a random rotation.", m)
+/// ```
+///
+/// [^1]: Shoemake, Ken (1992). *Uniform Random Rotations.* In: Graphics Gems
+///       III, pp. 124--132. Academic Press.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnitQuaternion;
+
+impl UnitQuaternion {
+    /// Convert the unit quaternion `[w, x, y, z]` to the equivalent 3×3
+    /// rotation matrix, as an array of rows.
+    ///
+    /// The result is orthonormal with determinant 1 (up to rounding),
+    /// provided that `q` has unit norm.
+    pub fn to_rotation_matrix<F: Float>(q: [F; 4]) -> [[F; 3]; 3] {
+        let [w, x, y, z] = q;
+        let one = F::one();
+        let two = one + one;
+        [
+            [
+                one - two * (y * y + z * z),
+                two * (x * y - z * w),
+                two * (x * z + y * w),
+            ],
+            [
+                two * (x * y + z * w),
+                one - two * (x * x + z * z),
+                two * (y * z - x * w),
+            ],
+            [
+                two * (x * z - y * w),
+                two * (y * z + x * w),
+                one - two * (x * x + y * y),
+            ],
+        ]
+    }
+}
+
+impl<F> Distribution<[F; 4]> for UnitQuaternion
+where F: Float + FloatConst, Standard: Distribution<F>
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [F; 4] {
+        let (u1, u2, u3): (F, F, F) = (rng.gen(), rng.gen(), rng.gen());
+        let (r1, r2) = ((F::one() - u1).sqrt(), u1.sqrt());
+        let two_pi = F::PI() + F::PI();
+        let (theta1, theta2) = (two_pi * u2, two_pi * u3);
+        [
+            r2 * theta2.cos(),
+            r1 * theta1.sin(),
+            r1 * theta1.cos(),
+            r2 * theta2.sin(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnitQuaternion;
+    use crate::Distribution;
+
+    #[test]
+    fn norm() {
+        let mut rng = crate::test::rng(255);
+        for _ in 0..1000 {
+            let q: [f64; 4] = UnitQuaternion.sample(&mut rng);
+            assert_almost_eq!(q.iter().map(|x| x * x).sum::<f64>(), 1., 1e-15);
+        }
+    }
+
+    #[test]
+    fn rotation_matrix() {
+        let identity = UnitQuaternion::to_rotation_matrix([1.0f64, 0.0, 0.0, 0.0]);
+        assert_eq!(identity, [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+
+        let mut rng = crate::test::rng(256);
+        for _ in 0..1000 {
+            let m: [[f64; 3]; 3] = UnitQuaternion::to_rotation_matrix(UnitQuaternion.sample(&mut rng));
+            // Rows are orthonormal, i.e. m * m^T = I
+            for i in 0..3 {
+                for j in 0..3 {
+                    let dot: f64 = (0..3).map(|k| m[i][k] * m[j][k]).sum();
+                    assert_almost_eq!(dot, if i == j { 1. } else { 0. }, 1e-14);
+                }
+            }
+            // A proper rotation, not a reflection
+            let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+            assert_almost_eq!(det, 1., 1e-14);
+        }
+    }
+
+    #[test]
+    fn uniform_rotation() {
+        // A uniformly rotated vector is uniform on the unit sphere: each
+        // coordinate has mean 0 and variance 1/3
+        const N: usize = 40_000;
+        let mut rng = crate::test::rng(257);
+        let mut sum = [0.0; 3];
+        let mut sum_sq = [0.0; 3];
+        for _ in 0..N {
+            let m: [[f64; 3]; 3] = UnitQuaternion::to_rotation_matrix(UnitQuaternion.sample(&mut rng));
+            // The image of the x axis is the first column
+            for i in 0..3 {
+                sum[i] += m[i][0];
+                sum_sq[i] += m[i][0] * m[i][0];
+            }
+        }
+        for i in 0..3 {
+            assert_almost_eq!(sum[i] / N as f64, 0., 0.01);
+            assert_almost_eq!(sum_sq[i] / N as f64, 1. / 3., 0.01);
+        }
+    }
+}
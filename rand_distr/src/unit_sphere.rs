@@ -60,4 +60,24 @@ mod tests {
             assert_almost_eq!(x[0] * x[0] + x[1] * x[1] + x[2] * x[2], 1., 1e-15);
         }
     }
+
+    #[test]
+    fn moments() {
+        const N: usize = 40_000;
+        let mut rng = crate::test::rng(254);
+        let mut sum = [0.0; 3];
+        let mut sum_sq = [0.0; 3];
+        for _ in 0..N {
+            let x: [f64; 3] = UnitSphere.sample(&mut rng);
+            for i in 0..3 {
+                sum[i] += x[i];
+                sum_sq[i] += x[i] * x[i];
+            }
+        }
+        // Each coordinate is uniform on [-1, 1], with mean 0 and variance 1/3
+        for i in 0..3 {
+            assert_almost_eq!(sum[i] / N as f64, 0., 0.01);
+            assert_almost_eq!(sum_sq[i] / N as f64, 1. / 3., 0.01);
+        }
+    }
 }
@@ -91,6 +91,14 @@ mod tests {
         Weibull::new(0., 0.).unwrap();
     }
 
+    #[test]
+    fn invalid_params() {
+        assert_eq!(Weibull::new(0., 1.).unwrap_err(), Error::ScaleTooSmall);
+        assert_eq!(Weibull::new(-1., 1.).unwrap_err(), Error::ScaleTooSmall);
+        assert_eq!(Weibull::new(1., 0.).unwrap_err(), Error::ShapeTooSmall);
+        assert_eq!(Weibull::new(1., ::core::f64::NAN).unwrap_err(), Error::ShapeTooSmall);
+    }
+
     #[test]
     fn sample() {
         let scale = 1.0;
@@ -74,6 +74,7 @@ pub struct WeightedAliasIndex<W: AliasableWeight> {
     no_alias_odds: Box<[W]>,
     uniform_index: Uniform<u32>,
     uniform_within_weight_sum: Uniform<W>,
+    weight_sum: W,
 }
 
 impl<W: AliasableWeight> WeightedAliasIndex<W> {
@@ -211,12 +212,17 @@ impl<W: AliasableWeight> WeightedAliasIndex<W> {
         }
 
         // The remaining indices should have no alias odds of about 100%. This is due to
-        // numeric accuracy. Otherwise they would be exactly 100%.
+        // numeric accuracy. Otherwise they would be exactly 100%. Their alias is never
+        // used, but we point it at the index itself to keep the table valid.
         while !aliases.smalls_is_empty() {
-            no_alias_odds[aliases.pop_small() as usize] = weight_sum;
+            let s = aliases.pop_small();
+            no_alias_odds[s as usize] = weight_sum;
+            aliases.set_alias(s, s);
         }
         while !aliases.bigs_is_empty() {
-            no_alias_odds[aliases.pop_big() as usize] = weight_sum;
+            let b = aliases.pop_big();
+            no_alias_odds[b as usize] = weight_sum;
+            aliases.set_alias(b, b);
         }
 
         // Prepare distributions for sampling. Creating them beforehand improves
@@ -229,8 +235,83 @@ impl<W: AliasableWeight> WeightedAliasIndex<W> {
             no_alias_odds,
             uniform_index,
             uniform_within_weight_sum,
+            weight_sum,
+        })
+    }
+
+    /// Reconstructs a [`WeightedAliasIndex`] from the tables returned by
+    /// [`WeightedAliasIndex::into_parts`].
+    ///
+    /// This allows large tables to be persisted (e.g. in a memory-mapped
+    /// file) and reloaded without repeating the construction in
+    /// [`WeightedAliasIndex::new`]. Sampling from the result is identical to
+    /// sampling from the original distribution.
+    ///
+    /// Returns an error if:
+    /// - The tables are empty.
+    /// - The tables are longer than `u32::MAX`.
+    /// - The tables have different lengths or any alias is out of bounds.
+    /// - `weight_sum` is zero or not finite.
+    /// - Any no-alias odds are not in the range `[0, weight_sum]`.
+    pub fn from_parts(
+        aliases: Vec<u32>, no_alias_odds: Vec<W>, weight_sum: W,
+    ) -> Result<Self, WeightedError> {
+        let n = aliases.len();
+        if n == 0 {
+            return Err(WeightedError::NoItem);
+        } else if n > ::core::u32::MAX as usize {
+            return Err(WeightedError::TooMany);
+        }
+        if no_alias_odds.len() != n || !aliases.iter().all(|&a| (a as usize) < n) {
+            return Err(WeightedError::InvalidWeight);
+        }
+        if !(weight_sum > W::ZERO) {
+            return Err(WeightedError::AllWeightsZero);
+        }
+        // Comparisons with NaN are false, so NaN odds are rejected too
+        if weight_sum > W::MAX
+            || !no_alias_odds.iter().all(|&o| o >= W::ZERO && o <= weight_sum)
+        {
+            return Err(WeightedError::InvalidWeight);
+        }
+
+        Ok(Self {
+            aliases: aliases.into_boxed_slice(),
+            no_alias_odds: no_alias_odds.into_boxed_slice(),
+            uniform_index: Uniform::new(0, n as u32),
+            uniform_within_weight_sum: Uniform::new(W::ZERO, weight_sum),
+            weight_sum,
         })
     }
+
+    /// Decomposes this distribution into its alias table, its table of
+    /// no-alias odds and the sum of weights.
+    ///
+    /// The result may be passed to [`WeightedAliasIndex::from_parts`].
+    pub fn into_parts(self) -> (Vec<u32>, Vec<W>, W) {
+        (
+            self.aliases.into_vec(),
+            self.no_alias_odds.into_vec(),
+            self.weight_sum,
+        )
+    }
+
+    /// Returns the alias of each index.
+    pub fn aliases(&self) -> &[u32] {
+        &self.aliases
+    }
+
+    /// Returns the odds of each index not being replaced by its alias,
+    /// relative to [`WeightedAliasIndex::weight_sum`].
+    pub fn no_alias_odds(&self) -> &[W] {
+        &self.no_alias_odds
+    }
+
+    /// Returns the sum of weights, against which the no-alias odds are
+    /// compared.
+    pub fn weight_sum(&self) -> W {
+        self.weight_sum
+    }
 }
 
 impl<W: AliasableWeight> Distribution<usize> for WeightedAliasIndex<W> {
@@ -255,6 +336,7 @@ where
             .field("no_alias_odds", &self.no_alias_odds)
             .field("uniform_index", &self.uniform_index)
             .field("uniform_within_weight_sum", &self.uniform_within_weight_sum)
+            .field("weight_sum", &self.weight_sum)
             .finish()
     }
 }
@@ -268,6 +350,7 @@ where Uniform<W>: Clone
             no_alias_odds: self.no_alias_odds.clone(),
             uniform_index: self.uniform_index,
             uniform_within_weight_sum: self.uniform_within_weight_sum.clone(),
+            weight_sum: self.weight_sum,
         }
     }
 }
@@ -504,6 +587,56 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_weighted_index_from_parts() {
+        let distr = WeightedAliasIndex::new(vec![1.0f64, 0.5, 3.0, 0.0, 2.5]).unwrap();
+        let (aliases, odds, weight_sum) = distr.clone().into_parts();
+        assert_eq!(distr.aliases(), &aliases[..]);
+        assert_eq!(distr.no_alias_odds(), &odds[..]);
+        assert_eq!(distr.weight_sum(), weight_sum);
+
+        let reloaded = WeightedAliasIndex::from_parts(aliases, odds, weight_sum).unwrap();
+        let mut rng1 = crate::test::rng(1683);
+        let mut rng2 = crate::test::rng(1683);
+        for _ in 0..100 {
+            assert_eq!(distr.sample(&mut rng1), reloaded.sample(&mut rng2));
+        }
+
+        assert_eq!(
+            WeightedAliasIndex::<u32>::from_parts(vec![], vec![], 1).unwrap_err(),
+            WeightedError::NoItem
+        );
+        assert_eq!(
+            WeightedAliasIndex::from_parts(vec![0, 0], vec![1u32], 1).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedAliasIndex::from_parts(vec![0, 2], vec![1u32, 1], 1).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedAliasIndex::from_parts(vec![0, 1], vec![0u32, 0], 0).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+        assert_eq!(
+            WeightedAliasIndex::from_parts(vec![0], vec![1.0], ::core::f64::INFINITY).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedAliasIndex::from_parts(vec![0, 1], vec![1u32, 3], 2).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedAliasIndex::from_parts(vec![0, 1], vec![-1.0, 1.0], 2.0).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedAliasIndex::from_parts(vec![0, 1], vec![::core::f64::NAN, 1.0], 2.0)
+                .unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<W: AliasableWeight>(weights: Vec<W>, buf: &mut [usize], expected: &[usize]) {
@@ -0,0 +1,157 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Integer-only weighted index sampling
+
+use super::WeightedError;
+use crate::{Distribution, Uniform};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use rand::Rng;
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// A distribution using weighted sampling of discrete items, with `u32`
+/// weights and no floating-point arithmetic.
+///
+/// Sampling an `IntWeightedIndex` returns the index of a randomly selected
+/// element of the weights used to construct it, where the chance of index `i`
+/// being picked is exactly `weights[i] / sum(weights)`. Construction and
+/// sampling use only integer arithmetic, making this suitable for
+/// deterministic environments without floating-point support.
+///
+/// # Performance
+///
+/// Creating an `IntWeightedIndex` allocates a table of `N` cumulative weights.
+/// Sampling is `O(log N)`: a single sample from a [`Uniform<u32>`] followed
+/// by a binary search of the cumulative table.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand_distr::IntWeightedIndex;
+///
+/// // A loaded die which rolls a six half of the time
+/// let die = IntWeightedIndex::new(&[1, 1, 1, 1, 1, 5]).unwrap();
+/// let roll = die.sample(&mut thread_rng()) + 1;
+/// println!("{}", roll);
+/// ```
+///
+/// [`Uniform<u32>`]: crate::Uniform
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct IntWeightedIndex {
+    cumulative_weights: Vec<u32>,
+    weight_distribution: Uniform<u32>,
+}
+
+impl IntWeightedIndex {
+    /// Creates a new `IntWeightedIndex` using the values in `weights`.
+    ///
+    /// Returns an error if `weights` is empty, if all weights are zero or if
+    /// the sum of the weights overflows a `u32`.
+    pub fn new(weights: &[u32]) -> Result<IntWeightedIndex, WeightedError> {
+        if weights.is_empty() {
+            return Err(WeightedError::NoItem);
+        }
+
+        let mut cumulative_weights = Vec::with_capacity(weights.len());
+        let mut total_weight = 0u32;
+        for &w in weights {
+            total_weight = total_weight
+                .checked_add(w)
+                .ok_or(WeightedError::InvalidWeight)?;
+            cumulative_weights.push(total_weight);
+        }
+
+        if total_weight == 0 {
+            return Err(WeightedError::AllWeightsZero);
+        }
+
+        Ok(IntWeightedIndex {
+            cumulative_weights,
+            weight_distribution: Uniform::new(0, total_weight),
+        })
+    }
+
+    /// Returns the sum of all weights.
+    pub fn total_weight(&self) -> u32 {
+        // The table is never empty; see `new`.
+        self.cumulative_weights[self.cumulative_weights.len() - 1]
+    }
+}
+
+impl Distribution<usize> for IntWeightedIndex {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let chosen_weight = self.weight_distribution.sample(rng);
+        // Find the first item whose cumulative weight is *higher* than the
+        // chosen weight. Zero-weight items share their cumulative weight with
+        // the preceding item and hence are never selected.
+        self.cumulative_weights
+            .binary_search_by(|&w| {
+                if w <= chosen_weight {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            })
+            .unwrap_err()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_int_weighted_index() {
+        const N_REPS: u32 = 12000;
+        let weights = [1u32, 2, 0, 3, 6];
+        let total_weight: u32 = weights.iter().sum();
+        let distr = IntWeightedIndex::new(&weights).unwrap();
+        assert_eq!(distr.total_weight(), total_weight);
+
+        let mut rng = crate::test::rng(0x1676);
+        let mut counts = [0u32; 5];
+        for _ in 0..N_REPS {
+            counts[distr.sample(&mut rng)] += 1;
+        }
+
+        assert_eq!(counts[2], 0);
+        for (&count, &w) in counts.iter().zip(weights.iter()) {
+            let expected = (w * N_REPS / total_weight) as f64;
+            let err = (count as f64 - expected).abs();
+            assert!(err <= 0.1 * N_REPS as f64 / weights.len() as f64);
+        }
+
+        for _ in 0..5 {
+            assert_eq!(IntWeightedIndex::new(&[0, 1]).unwrap().sample(&mut rng), 1);
+            assert_eq!(IntWeightedIndex::new(&[1, 0]).unwrap().sample(&mut rng), 0);
+        }
+    }
+
+    #[test]
+    fn test_int_weighted_index_errors() {
+        assert_eq!(
+            IntWeightedIndex::new(&[]).unwrap_err(),
+            WeightedError::NoItem
+        );
+        assert_eq!(
+            IntWeightedIndex::new(&[0, 0]).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+        assert_eq!(
+            IntWeightedIndex::new(&[::core::u32::MAX, 1]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert!(IntWeightedIndex::new(&[::core::u32::MAX, 0]).is_ok());
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The [`Arbitrary`] trait
+
+use crate::Rng;
+#[cfg(feature = "alloc")] use alloc::string::String;
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+
+/// The maximum length of collections generated by [`Arbitrary`].
+#[cfg(feature = "alloc")]
+const MAX_LEN: usize = 32;
+
+/// Types which may be generated as structured random test data.
+///
+/// This is a lightweight alternative to the `Arbitrary` traits of property
+/// testing crates, intended for simple randomized tests. Values are usually
+/// generated via [`Rng::gen_arbitrary`].
+///
+/// Implementations are provided for:
+///
+/// - Primitive types (integers, floats, `bool` and `char`): sampled from
+///   the [`Standard`] distribution.
+/// - `Option<T>`: `None` or `Some` with equal probability.
+/// - `Vec<T>` and `String` (requires `alloc`): a uniformly random length in
+///   `0..=32`, with each element generated independently.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(dead_code)]
+/// use rand::{Arbitrary, Rng};
+///
+/// #[derive(Debug)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl Arbitrary for Point {
+///     fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+///         Point { x: rng.gen_arbitrary(), y: rng.gen_arbitrary() }
+///     }
+/// }
+///
+/// let points: Vec<Point> = rand::thread_rng().gen_arbitrary();
+/// println!("{:?}", points);
+/// ```
+///
+/// [`Standard`]: crate::distributions::Standard
+pub trait Arbitrary: Sized {
+    /// Generate an arbitrary value of this type.
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self;
+}
+
+macro_rules! impl_arbitrary_via_standard {
+    ($($t:ty),*) => {
+        $(
+            impl Arbitrary for $t {
+                #[inline]
+                fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                    rng.gen()
+                }
+            }
+        )*
+    }
+}
+
+impl_arbitrary_via_standard!(u8, u16, u32, u64, u128, usize);
+impl_arbitrary_via_standard!(i8, i16, i32, i64, i128, isize);
+impl_arbitrary_via_standard!(f32, f64, bool, char);
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        if rng.gen() {
+            Some(T::arbitrary(rng))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..=MAX_LEN);
+        (0..len).map(|_| T::arbitrary(rng)).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl Arbitrary for String {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..=MAX_LEN);
+        (0..len).map(|_| char::arbitrary(rng)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+
+    #[test]
+    fn test_arbitrary_primitives() {
+        let mut rng = StepRng::new(7, 0);
+        assert_eq!(u32::arbitrary(&mut rng), 7);
+        assert_eq!(rng.gen_arbitrary::<u64>(), 7);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_arbitrary_vec() {
+        let mut rng = crate::test::rng(1723);
+        let mut lens = [false; MAX_LEN + 1];
+        let mut odd = 0;
+        let mut total = 0;
+        for _ in 0..1000 {
+            let v: Vec<u32> = rng.gen_arbitrary();
+            assert!(v.len() <= MAX_LEN);
+            lens[v.len()] = true;
+            odd += v.iter().filter(|&&x| x % 2 == 1).count();
+            total += v.len();
+        }
+        // All lengths occur, and elements cover the full range of `u32`
+        assert!(lens.iter().all(|&seen| seen));
+        assert!((odd as f64 / total as f64 - 0.5).abs() < 0.05);
+
+        let v: Vec<Option<u8>> = rng.gen_arbitrary();
+        assert!(v.len() <= MAX_LEN);
+        let s: String = rng.gen_arbitrary();
+        assert!(s.chars().count() <= MAX_LEN);
+    }
+
+    #[test]
+    fn test_arbitrary_option() {
+        let mut rng = crate::test::rng(1724);
+        let some = (0..1000)
+            .filter(|_| rng.gen_arbitrary::<Option<bool>>().is_some())
+            .count();
+        assert!(some > 400 && some < 600);
+    }
+}
@@ -9,8 +9,13 @@
 
 //! Distribution trait and associates
 
-use crate::Rng;
+use crate::distributions::{Bernoulli, Standard, Uniform};
+use crate::{Error, Rng, RngCore};
 use core::iter;
+#[cfg(not(target_os = "emscripten"))]
+use core::num::NonZeroU128;
+use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+use core::time::Duration;
 #[cfg(feature = "alloc")]
 use alloc::string::String;
 
@@ -207,6 +212,180 @@ pub trait DistString {
     }
 }
 
+/// Distributions which may be sampled using a fallible generator.
+///
+/// [`Distribution::sample`] assumes that the generator cannot fail. Where a
+/// generator wraps an unreliable source (e.g. a hardware TRNG), `try_sample`
+/// instead draws all randomness via [`RngCore::try_fill_bytes`] and returns
+/// the first error encountered, if any, instead of panicking.
+///
+/// Results may differ from [`Distribution::sample`] with the same generator,
+/// since the generator's `next_u32` and `next_u64` methods are not used.
+///
+/// This is implemented for [`Standard`] and [`Uniform`] over primitive types
+/// (and for [`Standard`] over small tuples of these), and for [`Bernoulli`].
+/// Sampling stops at the first error; in particular, rejection sampling
+/// (as for the `NonZero*` types) does not retry after an error.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{TryDistribution, Uniform};
+///
+/// let mut rng = rand::thread_rng();
+/// let die = Uniform::new_inclusive(1, 6);
+/// match die.try_sample(&mut rng) {
+///     Ok(roll) => println!("{}", roll),
+///     Err(e) => println!("the generator failed: {}", e),
+/// }
+/// ```
+pub trait TryDistribution<T> {
+    /// Try to generate a random value of `T`, using `rng` as the source of
+    /// randomness, returning the first error reported by `rng`.
+    fn try_sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<T, Error>;
+}
+
+/// Adapts a fallible generator, recording its first error.
+///
+/// After an error all output is zero. Samplers which reject some values in a
+/// loop might then never terminate (e.g. `Standard` for `NonZeroU32`), hence
+/// `TryRng` must only be used with samplers which are known to accept
+/// all-zero input: see the implementations of [`TryDistribution`] below.
+struct TryRng<'a, R: ?Sized> {
+    rng: &'a mut R,
+    error: Option<Error>,
+}
+
+impl<'a, R: RngCore + ?Sized> TryRng<'a, R> {
+    fn sample<T, D: Distribution<T>>(rng: &'a mut R, distr: &D) -> Result<T, Error> {
+        let mut try_rng = TryRng { rng, error: None };
+        let value = distr.sample(&mut try_rng);
+        match try_rng.error {
+            None => Ok(value),
+            Some(e) => Err(e),
+        }
+    }
+}
+
+impl<'a, R: RngCore + ?Sized> RngCore for TryRng<'a, R> {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if self.error.is_none() {
+            match self.rng.try_fill_bytes(dest) {
+                Ok(()) => return,
+                Err(e) => self.error = Some(e),
+            }
+        }
+        for x in dest.iter_mut() {
+            *x = 0;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+// Implementations via `TryRng` are limited to types whose samplers accept
+// all-zero input, and thus terminate after an error.
+macro_rules! try_distribution_impl {
+    ($($ty:ty),*) => {$(
+        impl TryDistribution<$ty> for Standard {
+            fn try_sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<$ty, Error> {
+                TryRng::sample(rng, self)
+            }
+        }
+
+        impl TryDistribution<$ty> for Uniform<$ty> {
+            fn try_sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<$ty, Error> {
+                TryRng::sample(rng, self)
+            }
+        }
+    )*}
+}
+
+try_distribution_impl!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, char);
+#[cfg(not(target_os = "emscripten"))]
+try_distribution_impl!(u128, i128);
+
+impl TryDistribution<bool> for Standard {
+    fn try_sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<bool, Error> {
+        TryRng::sample(rng, self)
+    }
+}
+
+impl TryDistribution<Duration> for Uniform<Duration> {
+    fn try_sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<Duration, Error> {
+        TryRng::sample(rng, self)
+    }
+}
+
+// `Standard` rejects zero for these, so samples are drawn until non-zero,
+// stopping at the first error.
+macro_rules! try_distribution_nonzero_impl {
+    ($($ty:ty, $new:path, $int:ty;)*) => {$(
+        impl TryDistribution<$ty> for Standard {
+            fn try_sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<$ty, Error> {
+                loop {
+                    let x: $int = self.try_sample(rng)?;
+                    if let Some(nz) = $new(x) {
+                        return Ok(nz);
+                    }
+                }
+            }
+        }
+    )*}
+}
+
+try_distribution_nonzero_impl! {
+    NonZeroU8, NonZeroU8::new, u8;
+    NonZeroU16, NonZeroU16::new, u16;
+    NonZeroU32, NonZeroU32::new, u32;
+    NonZeroU64, NonZeroU64::new, u64;
+    NonZeroUsize, NonZeroUsize::new, usize;
+}
+#[cfg(not(target_os = "emscripten"))]
+try_distribution_nonzero_impl! {
+    NonZeroU128, NonZeroU128::new, u128;
+}
+
+macro_rules! try_distribution_tuple_impl {
+    ($($tyvar:ident),*) => {
+        impl<$($tyvar),*> TryDistribution<($($tyvar),*,)> for Standard
+        where $(Standard: TryDistribution<$tyvar>),*
+        {
+            fn try_sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<($($tyvar),*,), Error> {
+                Ok(($(TryDistribution::<$tyvar>::try_sample(self, rng)?),*,))
+            }
+        }
+    }
+}
+
+try_distribution_tuple_impl! {A}
+try_distribution_tuple_impl! {A, B}
+try_distribution_tuple_impl! {A, B, C}
+try_distribution_tuple_impl! {A, B, C, D}
+try_distribution_tuple_impl! {A, B, C, D, E}
+try_distribution_tuple_impl! {A, B, C, D, E, F}
+
+impl TryDistribution<bool> for Bernoulli {
+    fn try_sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<bool, Error> {
+        TryRng::sample(rng, self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::distributions::{Alphanumeric, Distribution, Standard, Uniform};
@@ -269,4 +448,68 @@ mod tests {
         assert_eq!(s2.chars().count(), 20);
         assert_eq!(str::from_utf8(s2.as_bytes()), Ok(s2.as_str()));
     }
+
+    #[test]
+    fn test_try_distribution() {
+        use crate::distributions::{Bernoulli, TryDistribution};
+        use crate::{Error, RngCore};
+        use core::num::{NonZeroU32, NonZeroU64};
+
+        // Yields bytes from `try_fill_bytes` until `calls_left` reaches zero
+        struct FailingRng {
+            calls_left: usize,
+        }
+        impl RngCore for FailingRng {
+            fn next_u32(&mut self) -> u32 {
+                panic!("next_u32 called")
+            }
+            fn next_u64(&mut self) -> u64 {
+                panic!("next_u64 called")
+            }
+            fn fill_bytes(&mut self, _: &mut [u8]) {
+                panic!("fill_bytes called")
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                if self.calls_left == 0 {
+                    return Err(Error::from(NonZeroU32::new(Error::CUSTOM_START + 7).unwrap()));
+                }
+                self.calls_left -= 1;
+                for (i, x) in dest.iter_mut().enumerate() {
+                    *x = 0x5a ^ i as u8;
+                }
+                Ok(())
+            }
+        }
+
+        let mut rng = FailingRng { calls_left: 3 };
+        let distr = Uniform::new(10u32, 20);
+        for _ in 0..3 {
+            let x = distr.try_sample(&mut rng).unwrap();
+            assert!((10..20).contains(&x));
+        }
+        let err = distr.try_sample(&mut rng).unwrap_err();
+        assert_eq!(err.code(), NonZeroU32::new(Error::CUSTOM_START + 7));
+
+        let mut rng = FailingRng { calls_left: 1 };
+        let x: f64 = Standard.try_sample(&mut rng).unwrap();
+        assert!((0.0..1.0).contains(&x));
+        assert!(TryDistribution::<(u32, u64)>::try_sample(&Standard, &mut rng).is_err());
+
+        // A tuple needs two draws, the second of which fails
+        let mut rng = FailingRng { calls_left: 1 };
+        assert!(TryDistribution::<(u32, u32)>::try_sample(&Standard, &mut rng).is_err());
+
+        let mut rng = FailingRng { calls_left: 1 };
+        let distr = Bernoulli::new(0.5).unwrap();
+        assert!(distr.try_sample(&mut rng).is_ok());
+        assert!(distr.try_sample(&mut rng).is_err());
+
+        // `Standard` rejects zero for `NonZeroU32`; this must not loop forever
+        let mut rng = FailingRng { calls_left: 0 };
+        let err = TryDistribution::<NonZeroU32>::try_sample(&Standard, &mut rng).unwrap_err();
+        assert_eq!(err.code(), NonZeroU32::new(Error::CUSTOM_START + 7));
+        let mut rng = FailingRng { calls_left: 1 };
+        assert!(TryDistribution::<NonZeroU32>::try_sample(&Standard, &mut rng).is_ok());
+        assert!(TryDistribution::<(u8, NonZeroU64)>::try_sample(&Standard, &mut rng).is_err());
+    }
 }
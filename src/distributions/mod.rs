@@ -98,8 +98,10 @@ mod distribution;
 mod float;
 mod integer;
 mod other;
+#[cfg(feature = "min_const_gen")]
+mod permutation;
 mod slice;
-mod utils;
+pub(crate) mod utils;
 #[cfg(feature = "alloc")]
 mod weighted_index;
 
@@ -117,11 +119,13 @@ pub mod uniform;
 pub mod weighted;
 
 pub use self::bernoulli::{Bernoulli, BernoulliError};
-pub use self::distribution::{Distribution, DistIter, DistMap};
+pub use self::distribution::{Distribution, DistIter, DistMap, TryDistribution};
 #[cfg(feature = "alloc")]
 pub use self::distribution::DistString;
 pub use self::float::{Open01, OpenClosed01};
 pub use self::other::Alphanumeric;
+#[cfg(feature = "min_const_gen")]
+pub use self::permutation::Permutation;
 pub use self::slice::Slice;
 #[doc(inline)]
 pub use self::uniform::Uniform;
@@ -151,6 +155,9 @@ use crate::Rng;
 ///   half-open range `[0, 1)`. See notes below.
 /// * Wrapping integers (`Wrapping<T>`), besides the type identical to their
 ///   normal integer variants.
+/// * IP addresses (`Ipv4Addr` and `Ipv6Addr`, requires `std`): Uniformly
+///   distributed over all addresses. `IpAddr` first generates a `bool` to
+///   choose between a v4 and a v6 address.
 ///
 /// The `Standard` distribution also supports generation of the following
 /// compound types where all component types are supported:
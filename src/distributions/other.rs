@@ -22,6 +22,8 @@ use crate::Rng;
 use serde::{Serialize, Deserialize};
 #[cfg(feature = "min_const_gen")]
 use std::mem::{self, MaybeUninit};
+#[cfg(feature = "std")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 
 // ----- Sampling distributions -----
@@ -249,6 +251,37 @@ where Standard: Distribution<T>
     }
 }
 
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl Distribution<Ipv4Addr> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ipv4Addr {
+        Ipv4Addr::from(rng.gen::<u32>())
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl Distribution<Ipv6Addr> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ipv6Addr {
+        Ipv6Addr::from(rng.gen::<u128>())
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl Distribution<IpAddr> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> IpAddr {
+        if rng.gen::<bool>() {
+            IpAddr::V4(rng.gen())
+        } else {
+            IpAddr::V6(rng.gen())
+        }
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -295,6 +328,27 @@ mod tests {
         assert!(!incorrect);
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_ip_addr() {
+        let mut rng = crate::test::rng(807);
+        let a: Ipv4Addr = rng.gen();
+        let b: Ipv4Addr = rng.gen();
+        assert!(a != b);
+        let a: Ipv6Addr = rng.gen();
+        let b: Ipv6Addr = rng.gen();
+        assert!(a != b);
+
+        let (mut v4, mut v6) = (false, false);
+        for _ in 0..100 {
+            match rng.gen::<IpAddr>() {
+                IpAddr::V4(_) => v4 = true,
+                IpAddr::V6(_) => v6 = true,
+            }
+        }
+        assert!(v4 && v6);
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<T: Copy + core::fmt::Debug + PartialEq, D: Distribution<T>>(
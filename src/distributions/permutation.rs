@@ -0,0 +1,130 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The permutation distribution.
+
+use crate::distributions::Distribution;
+use crate::Rng;
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// A distribution of uniformly random permutations of `0..N`.
+///
+/// Samples are arrays `[u8; N]` containing each of the values `0..N` exactly
+/// once, with each of the `N!` orderings equally likely. They are generated
+/// with a Fisher-Yates shuffle on the stack, hence no allocation is required.
+///
+/// Since elements are `u8`, `N` may be at most 256; constructing or sampling
+/// a `Permutation<N>` with larger `N` fails to compile:
+///
+/// ```compile_fail
+/// use rand::distributions::Permutation;
+///
+/// let distr = Permutation::<257>::new();
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::distributions::Permutation;
+///
+/// let order: [u8; 4] = rand::thread_rng().sample(Permutation::<4>::new());
+/// let mut sorted = order;
+/// sorted.sort();
+/// assert_eq!(sorted, [0, 1, 2, 3]);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "min_const_gen")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Permutation<const N: usize> {
+    _private: (),
+}
+
+impl<const N: usize> Permutation<N> {
+    // Evaluating this fails (at compile time) if `N > 256`
+    const CHECK_N: usize = 256 - N;
+
+    /// Construct a new `Permutation` distribution.
+    pub fn new() -> Self {
+        let _ = Self::CHECK_N;
+        Permutation { _private: () }
+    }
+}
+
+impl<const N: usize> Default for Permutation<N> {
+    fn default() -> Self {
+        Permutation::new()
+    }
+}
+
+impl<const N: usize> Distribution<[u8; N]> for Permutation<N> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [u8; N] {
+        // Also checked here, since `Deserialize` bypasses `new`
+        let _ = Self::CHECK_N;
+        let mut result = [0u8; N];
+        for (i, x) in result.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        for i in (1..N).rev() {
+            let j = crate::seq::gen_index(rng, i + 1);
+            result.swap(i, j);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Index of a permutation of `0..N` in `0..N!` via its Lehmer code
+    fn rank<const N: usize>(p: &[u8; N]) -> usize {
+        let mut rank = 0;
+        for i in 0..N {
+            let smaller = p[i + 1..].iter().filter(|&&x| x < p[i]).count();
+            rank = rank * (N - i) + smaller;
+        }
+        rank
+    }
+
+    fn check_uniform<const N: usize, const F: usize>(seed: u64) {
+        let mut rng = crate::test::rng(seed);
+        let distr = Permutation::<N>::new();
+        let mut counts = [0u32; F];
+        let draws = 200 * F as u32;
+        for _ in 0..draws {
+            let p = distr.sample(&mut rng);
+            let mut seen = [false; N];
+            for &x in p.iter() {
+                assert!(!seen[x as usize]);
+                seen[x as usize] = true;
+            }
+            counts[rank(&p)] += 1;
+        }
+        for &c in counts.iter() {
+            assert!(c > 150 && c < 250);
+        }
+    }
+
+    #[test]
+    fn test_permutation() {
+        check_uniform::<1, 1>(260);
+        check_uniform::<3, 6>(261);
+        check_uniform::<4, 24>(262);
+
+        let mut rng = crate::test::rng(263);
+        let empty: [u8; 0] = Permutation::<0>::new().sample(&mut rng);
+        assert_eq!(empty, []);
+        let mut p = Permutation::<256>::new().sample(&mut rng);
+        p.sort_unstable();
+        for (i, &x) in p.iter().enumerate() {
+            assert_eq!(x as usize, i);
+        }
+    }
+}
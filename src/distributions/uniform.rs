@@ -15,9 +15,10 @@
 //! [`Uniform`].
 //!
 //! This distribution is provided with support for several primitive types
-//! (all integer and floating-point types) as well as [`std::time::Duration`],
-//! and supports extension to user-defined types via a type-specific *back-end*
-//! implementation.
+//! (all integer and floating-point types) as well as [`std::time::Duration`]
+//! and, with the `std` feature, [`std::net::Ipv4Addr`] and
+//! [`std::net::Ipv6Addr`] (treated as integers), and supports extension to
+//! user-defined types via a type-specific *back-end* implementation.
 //!
 //! The types [`UniformInt`], [`UniformFloat`] and [`UniformDuration`] are the
 //! back-ends supporting sampling from primitive integer and floating-point
@@ -105,6 +106,7 @@
 
 #[cfg(not(feature = "std"))] use core::time::Duration;
 #[cfg(feature = "std")] use std::time::Duration;
+#[cfg(feature = "std")] use std::net::{Ipv4Addr, Ipv6Addr};
 use core::ops::{Range, RangeInclusive};
 
 use crate::distributions::float::IntoFloat;
@@ -416,6 +418,11 @@ impl<T: SampleUniform + PartialOrd> SampleRange<T> for RangeInclusive<T> {
 /// An alternative to using a modulus is widening multiply: After a widening
 /// multiply by `range`, the result is in the high word. Then comparing the low
 /// word against `zone` makes sure our distribution is uniform.
+///
+/// Where `range` is a power of 2, `ints_to_reject` is zero and no sample is
+/// ever rejected. [`UniformSampler::sample`] detects this case and replaces
+/// the widening multiply with a shift, taking the most significant bits of
+/// the sample; the results are identical.
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct UniformInt<X> {
@@ -424,6 +431,26 @@ pub struct UniformInt<X> {
     z: X, // either ints_to_reject or zone depending on implementation
 }
 
+/// The method used to map random integers onto a range.
+///
+/// [`Uniform`] and [`Rng::gen_range`] always use [`IntSampleStrategy::Lemire`];
+/// the other strategies are only available via
+/// [`UniformInt::sample_single_inclusive_with`] and are intended for
+/// benchmarking which method is fastest on a given platform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntSampleStrategy {
+    /// Widening multiply with rejection of a biased zone (Lemire's method).
+    /// This is unbiased and is the default.
+    Lemire,
+    /// Classic rejection sampling: reject samples outside the largest
+    /// multiple of the range, then reduce with a modulus. This is unbiased.
+    Rejection,
+    /// Reduce a single sample with a modulus, without rejection. This is
+    /// biased unless the range is a power of 2, and should not be used where
+    /// uniformity matters.
+    BiasedModulo,
+}
+
 macro_rules! uniform_int_impl {
     ($ty:ty, $unsigned:ident, $u_large:ident) => {
         impl SampleUniform for $ty {
@@ -486,6 +513,15 @@ macro_rules! uniform_int_impl {
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
                 let range = self.range as $unsigned as $u_large;
                 if range > 0 {
+                    if self.z == 0 {
+                        // `range` is a power of 2: no sample is rejected and
+                        // the high word of `v.wmul(range)` is just the most
+                        // significant bits of `v`. Shift in two steps, since
+                        // shifting by the full width overflows for `range == 1`.
+                        let v: $u_large = rng.gen();
+                        let hi = (v >> range.leading_zeros()) >> 1;
+                        return self.low.wrapping_add(hi as $ty);
+                    }
                     let unsigned_max = ::core::$u_large::MAX;
                     let zone = unsigned_max - (self.z as $unsigned as $u_large);
                     loop {
@@ -551,6 +587,48 @@ macro_rules! uniform_int_impl {
                 }
             }
         }
+
+        impl UniformInt<$ty> {
+            /// Sample a single value uniformly from the range `[low, high]`
+            /// using the given [`IntSampleStrategy`].
+            ///
+            /// With [`IntSampleStrategy::Lemire`] this is equivalent to
+            /// [`UniformSampler::sample_single_inclusive`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if `low > high`.
+            pub fn sample_single_inclusive_with<R: Rng + ?Sized>(
+                low: $ty, high: $ty, strategy: IntSampleStrategy, rng: &mut R,
+            ) -> $ty {
+                assert!(low <= high, "UniformInt::sample_single_inclusive_with: low > high");
+                let range = high.wrapping_sub(low).wrapping_add(1) as $unsigned as $u_large;
+                if range == 0 {
+                    return rng.gen();
+                }
+
+                match strategy {
+                    IntSampleStrategy::Lemire => {
+                        <Self as UniformSampler>::sample_single_inclusive(low, high, rng)
+                    }
+                    IntSampleStrategy::Rejection => {
+                        let unsigned_max: $u_large = ::core::$u_large::MAX;
+                        let ints_to_reject = (unsigned_max - range + 1) % range;
+                        let zone = unsigned_max - ints_to_reject;
+                        loop {
+                            let v: $u_large = rng.gen();
+                            if v <= zone {
+                                return low.wrapping_add((v % range) as $ty);
+                            }
+                        }
+                    }
+                    IntSampleStrategy::BiasedModulo => {
+                        let v: $u_large = rng.gen();
+                        low.wrapping_add((v % range) as $ty)
+                    }
+                }
+            }
+        }
     };
 }
 
@@ -803,6 +881,9 @@ impl UniformSampler for UniformChar {
 /// multiply and addition. Values produced this way have what equals 23 bits of
 /// random digits for an `f32`, and 52 for an `f64`.
 ///
+/// See [`UniformFloatHighPrecision`] for an alternative using the full
+/// precision of the floating-point type.
+///
 /// [`new`]: UniformSampler::new
 /// [`new_inclusive`]: UniformSampler::new_inclusive
 /// [`Standard`]: crate::distributions::Standard
@@ -813,6 +894,107 @@ pub struct UniformFloat<X> {
     scale: X,
 }
 
+/// A uniform distribution over a half open range of floating-point values,
+/// using the full precision of the type.
+///
+/// [`Uniform::new`] scales a random value from `[0, 1)` with a fixed
+/// granularity of `2^-53` for `f64` (`2^-24` for `f32`), hence values
+/// close to `low` are coarsely spaced relative to their magnitude. This
+/// distribution instead scales a value where every representable float
+/// in `[0, 1)` may occur, with probability proportional to the spacing
+/// around it. Where `low` is zero, this allows sampling small values with
+/// full precision.
+///
+/// Sampling is somewhat slower than with [`Uniform::new`]. Each value
+/// usually consumes a single `u32` for `f32` or `u64` for `f64` from the
+/// generator; an extra word is required with probability `2^-9` for
+/// `f32` and `2^-12` for `f64`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, uniform::UniformFloatHighPrecision};
+///
+/// let distr = UniformFloatHighPrecision::new(0.0, 1e-300);
+/// let x: f64 = distr.sample(&mut rand::thread_rng());
+/// assert!(0.0 <= x && x < 1e-300);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct UniformFloatHighPrecision<X> {
+    low: X,
+    scale: X,
+}
+
+impl<X: HighPrecisionFloat> UniformFloatHighPrecision<X> {
+    /// Create a new `UniformFloatHighPrecision` instance which samples from
+    /// the half open range `[low, high)`. Panics if `low >= high` or if
+    /// either bound is not finite.
+    pub fn new(low: X, high: X) -> UniformFloatHighPrecision<X> {
+        X::new_high_precision(low, high)
+    }
+}
+
+/// Floating-point types supported by [`UniformFloatHighPrecision`]: `f32`
+/// and `f64` (and SIMD float types).
+///
+/// This trait cannot be implemented outside of this crate.
+pub trait HighPrecisionFloat: private::Sealed + Sized {
+    #[doc(hidden)]
+    fn new_high_precision(low: Self, high: Self) -> UniformFloatHighPrecision<Self>;
+}
+
+mod private {
+    // This trait is public but unnameable outside of this crate, hence
+    // `HighPrecisionFloat` cannot be implemented for other types.
+    pub trait Sealed {}
+}
+
+/// Helper trait to sample a value in `[0, 1)` such that every representable
+/// value may occur, not just multiples of `EPSILON / 2`.
+trait HighPrecisionUnit: Sized {
+    fn sample_high_precision_unit<R: Rng + ?Sized>(rng: &mut R) -> Self;
+}
+
+macro_rules! high_precision_unit_impl {
+    ($ty:ident, $uty:ident, $fraction_bits:expr, $min_exponent:expr) => {
+        impl HighPrecisionUnit for $ty {
+            fn sample_high_precision_unit<R: Rng + ?Sized>(rng: &mut R) -> $ty {
+                let float_size = core::mem::size_of::<$ty>() as u32 * 8;
+                let exponent_bits = float_size - $fraction_bits;
+
+                // We use the most significant bits for the fraction, and the
+                // remaining bits to select the exponent: the value falls in
+                // `[2^-(k+1), 2^-k)` with probability `2^-(k+1)`, where `k`
+                // is the number of trailing zeros. Only if all of these bits
+                // are zero do we need to draw more words.
+                let value: $uty = rng.gen();
+                let fraction = value >> exponent_bits;
+                let mut zeros = (value | (1 << exponent_bits)).trailing_zeros();
+                if zeros == exponent_bits {
+                    loop {
+                        let value: $uty = rng.gen();
+                        zeros += value.trailing_zeros();
+                        if value != 0 || zeros as i32 > -1 - $min_exponent {
+                            break;
+                        }
+                    }
+                }
+
+                let exponent = -1 - zeros as i32;
+                if exponent < $min_exponent {
+                    // Subnormal results are flushed to zero
+                    return 0.0;
+                }
+                fraction.into_float_with_exponent(exponent)
+            }
+        }
+    };
+}
+
+high_precision_unit_impl! { f32, u32, 23, -126 }
+high_precision_unit_impl! { f64, u64, 52, -1022 }
+
 macro_rules! uniform_float_impl {
     ($ty:ty, $uty:ident, $f_scalar:ident, $u_scalar:ident, $bits_to_discard:expr) => {
         impl SampleUniform for $ty {
@@ -992,6 +1174,45 @@ macro_rules! uniform_float_impl {
                 }
             }
         }
+
+        impl Distribution<$ty> for UniformFloatHighPrecision<$ty> {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                let mut value0_1 = <$ty>::splat(0.0);
+                for i in 0..<$ty>::lanes() {
+                    value0_1 = value0_1.replace(i, $f_scalar::sample_high_precision_unit(rng));
+                }
+                value0_1 * self.scale + self.low
+            }
+        }
+
+        impl private::Sealed for $ty {}
+
+        impl HighPrecisionFloat for $ty {
+            fn new_high_precision(low: $ty, high: $ty) -> UniformFloatHighPrecision<$ty> {
+                assert!(
+                    low.all_finite() && high.all_finite(),
+                    "UniformFloatHighPrecision::new called with non-finite bounds"
+                );
+                assert!(
+                    low.all_lt(high),
+                    "UniformFloatHighPrecision::new called with `low >= high`"
+                );
+                let max_rand = <$ty>::splat(1.0 - ::core::$f_scalar::EPSILON / 2.0);
+
+                let mut scale = high - low;
+                assert!(scale.all_finite(), "UniformFloatHighPrecision::new: range overflow");
+
+                loop {
+                    let mask = (scale * max_rand + low).ge_mask(high);
+                    if mask.none() {
+                        break;
+                    }
+                    scale = scale.decrease_masked(mask);
+                }
+
+                UniformFloatHighPrecision { low, scale }
+            }
+        }
     };
 }
 
@@ -1147,6 +1368,62 @@ impl UniformSampler for UniformDuration {
     }
 }
 
+macro_rules! uniform_ip_impl {
+    ($ty:ty, $sampler:ident, $int:ty) => {
+        /// The back-end implementing [`UniformSampler`] for an IP address type.
+        ///
+        /// Addresses are sampled uniformly by treating them as integers.
+        /// Unless you are implementing [`UniformSampler`] for your own types,
+        /// this type should not be used directly, use [`Uniform`] instead.
+        #[cfg(feature = "std")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+        #[derive(Clone, Copy, Debug)]
+        #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+        pub struct $sampler(UniformInt<$int>);
+
+        #[cfg(feature = "std")]
+        impl SampleUniform for $ty {
+            type Sampler = $sampler;
+        }
+
+        #[cfg(feature = "std")]
+        impl UniformSampler for $sampler {
+            type X = $ty;
+
+            #[inline]
+            fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let low = <$int>::from(*low_b.borrow());
+                let high = <$int>::from(*high_b.borrow());
+                $sampler(UniformInt::<$int>::new(low, high))
+            }
+
+            #[inline]
+            fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let low = <$int>::from(*low_b.borrow());
+                let high = <$int>::from(*high_b.borrow());
+                $sampler(UniformInt::<$int>::new_inclusive(low, high))
+            }
+
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                <$ty>::from(self.0.sample(rng))
+            }
+        }
+    };
+}
+
+uniform_ip_impl! { Ipv4Addr, UniformIpv4Addr, u32 }
+#[cfg(not(target_os = "emscripten"))]
+uniform_ip_impl! { Ipv6Addr, UniformIpv6Addr, u128 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1311,6 +1588,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_uniform_int_power_of_two() {
+        // Enumerate the most significant bits, which are all that is used
+        let mut counts = [0usize; 256];
+        let mut step_rng = StepRng::new(0, 1 << 56);
+        let distr = Uniform::new(0u64, 256);
+        for _ in 0..256 {
+            counts[distr.sample(&mut step_rng) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| c == 1));
+
+        let mut counts = [0usize; 256];
+        let mut step_rng = StepRng::new(0, 1 << 24);
+        let distr = Uniform::new(-128i16, 128);
+        for _ in 0..256 {
+            counts[(distr.sample(&mut step_rng) + 128) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| c == 1));
+
+        // The shift must give the same results as the widening multiply
+        let mut rng = crate::test::rng(261);
+        for k in 0..64 {
+            let range = 1u64 << k;
+            let distr = Uniform::new(0u64, range);
+            for _ in 0..10 {
+                let v: u64 = rng.gen();
+                let x = distr.sample(&mut StepRng::new(v, 0));
+                assert_eq!(x, v.wmul(range).0);
+            }
+        }
+        assert_eq!(Uniform::new(5u32, 6).sample(&mut rng), 5);
+        assert_eq!(Uniform::new(5u8, 6).sample(&mut rng), 5);
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Miri is too slow
     fn test_char() {
@@ -1442,6 +1753,55 @@ mod tests {
         rng.gen_range(::core::f64::MIN..::core::f64::MAX);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_float_high_precision() {
+        let mut rng = crate::test::rng(260);
+
+        // Tiny ranges near zero
+        let distr = UniformFloatHighPrecision::new(0.0, 1e-300);
+        let mut values = [0.0f64; 1000];
+        for x in values.iter_mut() {
+            *x = distr.sample(&mut rng);
+            assert!((0.0..1e-300).contains(x));
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let distinct = 1 + values.windows(2).filter(|w| w[0] != w[1]).count();
+        assert!(distinct > 990);
+
+        // `Uniform::new` only yields multiples of 2^-53 in `[0, 1)`
+        const N: usize = 10_000;
+        let fine = |x: f64| (x * (1u64 << 53) as f64).fract() != 0.0;
+        let distr = Uniform::new(0.0, 1.0);
+        assert!(!(0..N).any(|_| fine(distr.sample(&mut rng))));
+        let distr = UniformFloatHighPrecision::new(0.0, 1.0);
+        let mut count = 0;
+        let mut sum = 0.0;
+        for _ in 0..N {
+            let x = distr.sample(&mut rng);
+            assert!((0.0..1.0).contains(&x));
+            sum += x;
+            if fine(x) {
+                count += 1;
+            }
+        }
+        // All values below 0.5 have finer spacing, most are not multiples
+        assert!(count > N / 4);
+        assert!((sum / N as f64 - 0.5).abs() < 0.02);
+
+        let distr = UniformFloatHighPrecision::new(-2.0f32, 3.0);
+        for _ in 0..1000 {
+            let x = distr.sample(&mut rng);
+            assert!((-2.0..3.0).contains(&x));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_float_high_precision_panic() {
+        UniformFloatHighPrecision::new(0.0, ::core::f64::INFINITY);
+    }
+
     #[test]
     #[cfg(all(
         feature = "std",
@@ -1529,6 +1889,33 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_ip_addrs() {
+        let mut rng = crate::test::rng(254);
+
+        let low = Ipv4Addr::new(10, 0, 0, 250);
+        let high = Ipv4Addr::new(10, 0, 1, 5);
+        for _ in 0..100 {
+            let v = rng.gen_range(low..high);
+            assert!(low <= v && v < high);
+            let v = rng.gen_range(low..=high);
+            assert!(low <= v && v <= high);
+        }
+        let one = Ipv4Addr::new(192, 168, 0, 1);
+        assert_eq!(rng.gen_range(one..=one), one);
+
+        #[cfg(not(target_os = "emscripten"))]
+        {
+            let low = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0);
+            let high = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 1, 0);
+            for _ in 0..100 {
+                let v = rng.gen_range(low..high);
+                assert!(low <= v && v < high);
+            }
+        }
+    }
+
     #[test]
     fn test_custom_uniform() {
         use crate::distributions::uniform::{
@@ -1599,6 +1986,55 @@ mod tests {
         assert!(r.0.scale < 5.0 + 1e-14);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_int_sample_strategies() {
+        const N: usize = 60_000;
+        macro_rules! t {
+            ($ty:ident, $low:expr, $high:expr) => {{
+                let (low, high): ($ty, $ty) = ($low, $high);
+                let n = (high - low) as usize + 1;
+                let mut counts = [[0usize; 6]; 3];
+                let strategies = [
+                    IntSampleStrategy::Lemire,
+                    IntSampleStrategy::Rejection,
+                    IntSampleStrategy::BiasedModulo,
+                ];
+                let mut rng = crate::test::rng(252);
+                for (c, &strategy) in counts.iter_mut().zip(strategies.iter()) {
+                    for _ in 0..N {
+                        let v = UniformInt::<$ty>::sample_single_inclusive_with(
+                            low, high, strategy, &mut rng,
+                        );
+                        assert!(low <= v && v <= high);
+                        c[(v - low) as usize] += 1;
+                    }
+                }
+                // The unbiased strategies agree with each other and with the
+                // expected frequency
+                let expected = (N / n) as f64;
+                for i in 0..n {
+                    for c in &counts[..2] {
+                        assert!((c[i] as f64 - expected).abs() < 0.05 * expected);
+                    }
+                }
+            }};
+        }
+        t!(u8, 10, 15);
+        t!(i16, -3, 2);
+        t!(u32, 0, 5);
+        t!(i64, -1, 4);
+
+        let mut rng = crate::test::rng(253);
+        assert_eq!(
+            UniformInt::<u8>::sample_single_inclusive_with(7, 7, IntSampleStrategy::Rejection, &mut rng),
+            7
+        );
+        let _: i32 = UniformInt::<i32>::sample_single_inclusive_with(
+            ::core::i32::MIN, ::core::i32::MAX, IntSampleStrategy::BiasedModulo, &mut rng,
+        );
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<T: SampleUniform + Copy + core::fmt::Debug + PartialEq>(
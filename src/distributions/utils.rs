@@ -431,3 +431,48 @@ macro_rules! simd_impl {
 #[cfg(feature="simd_support")] simd_impl! { f64x2, f64, m64x2, u64x2 }
 #[cfg(feature="simd_support")] simd_impl! { f64x4, f64, m64x4, u64x4 }
 #[cfg(feature="simd_support")] simd_impl! { f64x8, f64, m64x8, u64x8 }
+
+/// Sample from the standard normal distribution via the Marsaglia polar
+/// method.
+///
+/// This is slower than the ziggurat method used by `rand_distr`, but needs no
+/// tables; it supports the few `Rng` methods requiring normal variates.
+#[cfg(feature = "std")]
+pub(crate) fn sample_standard_normal<R: crate::Rng + ?Sized>(rng: &mut R) -> f64 {
+    loop {
+        let x = 2.0 * rng.gen::<f64>() - 1.0;
+        let y = 2.0 * rng.gen::<f64>() - 1.0;
+        let s = x * x + y * y;
+        if s < 1.0 && s > 0.0 {
+            return x * (-2.0 * s.ln() / s).sqrt();
+        }
+    }
+}
+
+/// Sample the natural logarithm of a `Gamma(shape, 1)` variate, for
+/// `shape > 0`.
+///
+/// This uses the method of Marsaglia and Tsang, with the boost
+/// `Gamma(a) = Gamma(a + 1) * U^(1/a)` for `shape < 1`. Working with the
+/// logarithm avoids underflow to zero for small shapes.
+#[cfg(feature = "std")]
+pub(crate) fn sample_ln_gamma<R: crate::Rng + ?Sized>(rng: &mut R, shape: f64) -> f64 {
+    if shape < 1.0 {
+        let u: f64 = rng.sample(super::Open01);
+        return sample_ln_gamma(rng, shape + 1.0) + u.ln() / shape;
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = sample_standard_normal(rng);
+        let v = 1.0 + c * x;
+        if v <= 0.0 {
+            continue;
+        }
+        let v = v * v * v;
+        let u: f64 = rng.sample(super::Open01);
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return (d * v).ln();
+        }
+    }
+}
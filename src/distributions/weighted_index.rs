@@ -15,6 +15,7 @@ use core::cmp::PartialOrd;
 use core::fmt;
 
 // Note that this whole module is only imported if feature="alloc" is enabled.
+use alloc::vec;
 use alloc::vec::Vec;
 
 #[cfg(feature = "serde1")]
@@ -31,7 +32,10 @@ use serde::{Serialize, Deserialize};
 /// # Performance
 ///
 /// Time complexity of sampling from `WeightedIndex` is `O(log N)` where
-/// `N` is the number of weights. As an alternative,
+/// `N` is the number of non-zero weights: items with zero weight are skipped
+/// at construction, so they are never sampled and do not slow down
+/// sampling. Sampled indices always refer to positions in the original
+/// weights. As an alternative,
 /// [`rand_distr::weighted_alias`](https://docs.rs/rand_distr/*/rand_distr/weighted_alias/index.html)
 /// supports `O(1)` sampling, but with much higher initialisation cost.
 ///
@@ -79,9 +83,16 @@ use serde::{Serialize, Deserialize};
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub struct WeightedIndex<X: SampleUniform + PartialOrd> {
+    // Cumulative weights of the non-zero weights, excluding the last
     cumulative_weights: Vec<X>,
     total_weight: X,
     weight_distribution: X::Sampler,
+    // Original index of each non-zero weight; `None` if no weight is zero
+    #[cfg_attr(feature = "serde1", serde(default))]
+    indices: Option<Vec<usize>>,
+    // Number of weights including zero weights; only used with `indices`
+    #[cfg_attr(feature = "serde1", serde(default))]
+    len: usize,
 }
 
 impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
@@ -99,26 +110,44 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
         I::Item: SampleBorrow<X>,
         X: for<'a> ::core::ops::AddAssign<&'a X> + Clone + Default,
     {
-        let mut iter = weights.into_iter();
-        let mut total_weight: X = iter.next().ok_or(WeightedError::NoItem)?.borrow().clone();
-
+        let iter = weights.into_iter();
         let zero = <X as Default>::default();
-        if !(total_weight >= zero) {
-            return Err(WeightedError::InvalidWeight);
-        }
 
+        let mut total_weight = zero.clone();
         let mut weights = Vec::<X>::with_capacity(iter.size_hint().0);
-        for w in iter {
+        let mut indices: Option<Vec<usize>> = None;
+        let mut len = 0;
+        let mut non_zero = 0;
+        for (i, w) in iter.enumerate() {
+            let w = w.borrow();
             // Note that `!(w >= x)` is not equivalent to `w < x` for partially
             // ordered types due to NaNs which are equal to nothing.
-            if !(w.borrow() >= &zero) {
+            if !(w >= &zero) {
                 return Err(WeightedError::InvalidWeight);
             }
-            weights.push(total_weight.clone());
-            total_weight += w.borrow();
+            len = i + 1;
+            if !(w > &zero) {
+                // Skip zero weights, tracking the original indices from the
+                // first one on. All previous weights are non-zero.
+                if indices.is_none() {
+                    indices = Some((0..i).collect());
+                }
+                continue;
+            }
+            if non_zero > 0 {
+                weights.push(total_weight.clone());
+            }
+            total_weight += w;
+            non_zero += 1;
+            if let Some(indices) = indices.as_mut() {
+                indices.push(i);
+            }
         }
 
-        if total_weight == zero {
+        if len == 0 {
+            return Err(WeightedError::NoItem);
+        }
+        if non_zero == 0 {
             return Err(WeightedError::AllWeightsZero);
         }
         let distr = X::Sampler::new(zero, total_weight.clone());
@@ -127,6 +156,8 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
             cumulative_weights: weights,
             total_weight,
             weight_distribution: distr,
+            indices,
+            len,
         })
     }
 
@@ -138,6 +169,12 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
     /// weights is modified. No allocations are performed, unless the weight type `X` uses
     /// allocation internally.
     ///
+    /// Weights which were zero on construction are skipped during sampling.
+    /// If such a weight is set to a non-zero value, the distribution is
+    /// rebuilt from the updated weights, which allocates. Weights which are
+    /// set to zero by this method are never sampled, but are only skipped
+    /// during sampling once the distribution is rebuilt.
+    ///
     /// In case of error, `self` is not modified.
     pub fn update_weights(&mut self, new_weights: &[(usize, &X)]) -> Result<(), WeightedError>
     where X: for<'a> ::core::ops::AddAssign<&'a X>
@@ -151,10 +188,22 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
         let zero = <X as Default>::default();
 
         let mut total_weight = self.total_weight.clone();
+        let len = match self.indices {
+            Some(_) => self.len,
+            None => self.cumulative_weights.len() + 1,
+        };
+        // Position of the weight with index `i` in `cumulative_weights`, or
+        // `None` if it was skipped for being zero
+        let indices = &self.indices;
+        let position = |i: usize| match *indices {
+            Some(ref indices) => indices.binary_search(&i).ok(),
+            None => Some(i),
+        };
 
         // Check for errors first, so we don't modify `self` in case something
         // goes wrong.
         let mut prev_i = None;
+        let mut rebuild = false;
         for &(i, w) in new_weights {
             if let Some(old_i) = prev_i {
                 if old_i >= i {
@@ -164,34 +213,50 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
             if !(*w >= zero) {
                 return Err(WeightedError::InvalidWeight);
             }
-            if i > self.cumulative_weights.len() {
+            if i >= len {
                 return Err(WeightedError::TooMany);
             }
+            prev_i = Some(i);
 
-            let mut old_w = if i < self.cumulative_weights.len() {
-                self.cumulative_weights[i].clone()
+            let k = match position(i) {
+                Some(k) => k,
+                None => {
+                    // A skipped weight must be included if it is non-zero
+                    rebuild |= *w > zero;
+                    continue;
+                }
+            };
+            let mut old_w = if k < self.cumulative_weights.len() {
+                self.cumulative_weights[k].clone()
             } else {
                 self.total_weight.clone()
             };
-            if i > 0 {
-                old_w -= &self.cumulative_weights[i - 1];
+            if k > 0 {
+                old_w -= &self.cumulative_weights[k - 1];
             }
 
             total_weight -= &old_w;
             total_weight += w;
-            prev_i = Some(i);
+        }
+        if rebuild {
+            return self.rebuild_with(new_weights);
         }
         if total_weight <= zero {
             return Err(WeightedError::AllWeightsZero);
         }
 
         // Update the weights. Because we checked all the preconditions in the
-        // previous loop, this should never panic.
-        let mut iter = new_weights.iter();
+        // previous loop, this should never panic. Skipped weights remain zero.
+        let mut iter = new_weights
+            .iter()
+            .filter_map(|&(i, w)| position(i).map(|k| (k, w)));
 
         let mut prev_weight = zero.clone();
         let mut next_new_weight = iter.next();
-        let &(first_new_index, _) = next_new_weight.unwrap();
+        let first_new_index = match next_new_weight {
+            Some((k, _)) => k,
+            None => return Ok(()),
+        };
         let mut cumulative_weight = if first_new_index > 0 {
             self.cumulative_weights[first_new_index - 1].clone()
         } else {
@@ -199,7 +264,7 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
         };
         for i in first_new_index..self.cumulative_weights.len() {
             match next_new_weight {
-                Some(&(j, w)) if i == j => {
+                Some((j, w)) if i == j => {
                     cumulative_weight += w;
                     next_new_weight = iter.next();
                 }
@@ -218,6 +283,38 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
 
         Ok(())
     }
+
+    /// Implementation of `update_weights` when a skipped zero weight becomes
+    /// non-zero: recover all weights, apply the updates and construct anew.
+    /// The updates must already be validated.
+    fn rebuild_with(&mut self, new_weights: &[(usize, &X)]) -> Result<(), WeightedError>
+    where X: for<'a> ::core::ops::AddAssign<&'a X>
+            + for<'a> ::core::ops::SubAssign<&'a X>
+            + Clone
+            + Default {
+        let zero = <X as Default>::default();
+        let indices = self.indices.as_ref().unwrap();
+
+        let mut weights = vec![zero.clone(); self.len];
+        let mut prev_weight = zero;
+        for (k, &i) in indices.iter().enumerate() {
+            let cumulative_weight = self
+                .cumulative_weights
+                .get(k)
+                .unwrap_or(&self.total_weight)
+                .clone();
+            weights[i] = cumulative_weight.clone();
+            weights[i] -= &prev_weight;
+            prev_weight = cumulative_weight;
+        }
+
+        for &(i, w) in new_weights {
+            weights[i] = w.clone();
+        }
+
+        *self = WeightedIndex::new(weights)?;
+        Ok(())
+    }
 }
 
 impl<X> Distribution<usize> for WeightedIndex<X>
@@ -227,7 +324,8 @@ where X: SampleUniform + PartialOrd
         use ::core::cmp::Ordering;
         let chosen_weight = self.weight_distribution.sample(rng);
         // Find the first item which has a weight *higher* than the chosen weight.
-        self.cumulative_weights
+        let index = self
+            .cumulative_weights
             .binary_search_by(|w| {
                 if *w <= chosen_weight {
                     Ordering::Less
@@ -235,7 +333,11 @@ where X: SampleUniform + PartialOrd
                     Ordering::Greater
                 }
             })
-            .unwrap_err()
+            .unwrap_err();
+        match self.indices {
+            Some(ref indices) => indices[index],
+            None => index,
+        }
     }
 }
 
@@ -389,6 +491,93 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_weightedindex_zero_weights() {
+        let mut r = crate::test::rng(702);
+        const N_REPS: usize = 10_000;
+        let weights = [0u32, 3, 0, 0, 1, 0, 4, 0];
+        let distr = WeightedIndex::new(weights.iter()).unwrap();
+        // Zero weights are skipped
+        assert_eq!(distr.cumulative_weights, [3, 4]);
+        assert_eq!(distr.indices, Some(vec![1, 4, 6]));
+
+        let mut chosen = [0usize; 8];
+        for _ in 0..N_REPS {
+            chosen[distr.sample(&mut r)] += 1;
+        }
+        for (i, &count) in chosen.iter().enumerate() {
+            if weights[i] == 0 {
+                assert_eq!(count, 0);
+            } else {
+                let p = weights[i] as f64 / 8.0;
+                assert!((count as f64 / N_REPS as f64 - p).abs() < 0.02);
+            }
+        }
+
+        // Without zero weights, no index mapping is needed
+        assert_eq!(WeightedIndex::new([1, 2].iter()).unwrap().indices, None);
+        assert_eq!(
+            WeightedIndex::new([0.0, -1.0].iter()).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+    }
+
+    #[test]
+    fn test_update_weights_with_zero_weights() {
+        let mut distr = WeightedIndex::new([0u32, 2, 0, 3].iter()).unwrap();
+        distr.update_weights(&[(0, &1), (3, &0)]).unwrap();
+        let expected = WeightedIndex::new([1u32, 2, 0, 0].iter()).unwrap();
+        assert_eq!(distr.cumulative_weights, expected.cumulative_weights);
+        assert_eq!(distr.total_weight, 3);
+        assert_eq!(distr.indices, Some(vec![0, 1]));
+        assert_eq!(distr.len, 4);
+
+        assert_eq!(
+            distr.update_weights(&[(4, &1)]).unwrap_err(),
+            WeightedError::TooMany
+        );
+        assert_eq!(
+            distr.update_weights(&[(2, &1), (1, &1)]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            distr.update_weights(&[(0, &0), (1, &0)]).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+        // Unchanged after errors
+        assert_eq!(distr.cumulative_weights, expected.cumulative_weights);
+
+        let mut r = crate::test::rng(703);
+        for _ in 0..100 {
+            assert!(distr.sample(&mut r) < 2);
+        }
+    }
+
+    #[test]
+    fn test_update_weights_in_place_with_zero_weights() {
+        let mut distr = WeightedIndex::new([0u32, 2, 0, 3, 1].iter()).unwrap();
+        let indices = distr.indices.clone();
+
+        // Non-zero weights are updated in place, skipped weights stay skipped
+        distr.update_weights(&[(0, &0), (3, &4), (4, &0)]).unwrap();
+        assert_eq!(distr.indices, indices);
+        assert_eq!(distr.cumulative_weights, [2, 6]);
+        assert_eq!(distr.total_weight, 6);
+
+        let mut r = crate::test::rng(704);
+        for _ in 0..100 {
+            let i = distr.sample(&mut r);
+            assert!(i == 1 || i == 3);
+        }
+
+        // A skipped weight becoming non-zero requires a rebuild
+        distr.update_weights(&[(2, &1)]).unwrap();
+        let expected = WeightedIndex::new([0u32, 2, 1, 4, 0].iter()).unwrap();
+        assert_eq!(distr.cumulative_weights, expected.cumulative_weights);
+        assert_eq!(distr.total_weight, 7);
+        assert_eq!(distr.indices, Some(vec![1, 2, 3]));
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<X: SampleUniform + PartialOrd, I>(
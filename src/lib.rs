@@ -95,6 +95,7 @@ macro_rules! error { ($($x:tt)*) => (
 pub use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 
 // Public modules
+mod arbitrary;
 pub mod distributions;
 pub mod prelude;
 mod rng;
@@ -103,7 +104,10 @@ pub mod seq;
 
 // Public exports
 #[cfg(all(feature = "std", feature = "std_rng"))]
-pub use crate::rngs::thread::thread_rng;
+pub use crate::rngs::thread::{thread_rng, thread_rng_entropy_source};
+#[cfg(feature = "testing")]
+pub use crate::rngs::thread::set_global_test_seed;
+pub use arbitrary::Arbitrary;
 pub use rng::{Fill, Rng};
 
 #[cfg(all(feature = "std", feature = "std_rng"))]
@@ -186,6 +190,31 @@ where Standard: Distribution<T> {
     thread_rng().gen()
 }
 
+/// Creates a small, reproducible generator seeded from `seed`.
+///
+/// This provides a one-line default generator where no source of entropy is
+/// available, e.g. on embedded `no_std` targets. Unlike [`rngs::SmallRng`],
+/// the algorithm is pinned (currently xoshiro256++, seeded via
+/// [`SeedableRng::seed_from_u64`]) and will only change in a breaking
+/// release, so the output for a given `seed` is reproducible.
+///
+/// This generator is **not** cryptographically secure.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+///
+/// let mut rng = rand::default_rng(1);
+/// let x: u32 = rng.gen_range(0..10);
+/// assert_eq!(rand::default_rng(1).gen_range(0..10), x);
+/// ```
+#[cfg(feature = "default_rng")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "default_rng")))]
+pub fn default_rng(seed: u64) -> impl RngCore {
+    rngs::xoshiro256plusplus::Xoshiro256PlusPlus::seed_from_u64(seed)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -212,4 +241,20 @@ mod test {
             (f32, (f64, (f64,))),
         ) = random();
     }
+
+    #[test]
+    #[cfg(feature = "default_rng")]
+    fn test_default_rng() {
+        let mut rng = default_rng(1);
+        let v: [u64; 3] = [rng.next_u64(), rng.next_u64(), rng.next_u64()];
+        assert_eq!(v, [
+            14971601782005023387,
+            13781649495232077965,
+            1847458086238483744,
+        ]);
+
+        let mut rng = default_rng(1);
+        assert_eq!(rng.next_u64(), v[0]);
+        assert_ne!(default_rng(2).next_u64(), v[0]);
+    }
 }
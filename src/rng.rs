@@ -10,10 +10,13 @@
 //! [`Rng`] trait
 
 use rand_core::{Error, RngCore};
+use crate::Arbitrary;
 use crate::distributions::uniform::{SampleRange, SampleUniform};
 use crate::distributions::{self, Distribution, Standard};
 use core::num::Wrapping;
 use core::{mem, slice};
+#[cfg(feature = "alloc")] use alloc::string::String;
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
 
 /// An automatically-implemented extension trait on [`RngCore`] providing high-level
 /// generic methods for sampling values and other convenience methods.
@@ -135,6 +138,33 @@ pub trait Rng: RngCore {
         range.sample_single(self)
     }
 
+    /// Generate a uniformly distributed element of `Z/nZ`, i.e. a value in
+    /// the range `[0, modulus)`.
+    ///
+    /// This is free of modulo bias: unlike `rng.gen::<u64>() % modulus`, all
+    /// values are exactly equally likely (given a perfect generator), which
+    /// is achieved by rejecting a small zone of random values. Results are
+    /// the same as `rng.gen_range(0..modulus)`.
+    ///
+    /// # Panics
+    ///
+    /// If `modulus == 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// // A random element of the field of order 2^61 - 1
+    /// let p = (1 << 61) - 1;
+    /// let x = thread_rng().gen_mod(p);
+    /// assert!(x < p);
+    /// ```
+    fn gen_mod(&mut self, modulus: u64) -> u64 {
+        assert!(modulus != 0, "gen_mod: modulus is zero");
+        self.gen_range(0..modulus)
+    }
+
     /// Sample a new value, using the given distribution.
     ///
     /// ### Example
@@ -247,6 +277,79 @@ pub trait Rng: RngCore {
         dest.try_fill(self)
     }
 
+    /// Fill `dest` with `f64` values sampled from the [`Standard`]
+    /// distribution, i.e. uniformly from `[0, 1)`.
+    ///
+    /// Raw words are obtained with one call to [`fill_bytes`] per chunk of 32
+    /// values and then converted in a tight loop which the compiler can
+    /// vectorize, instead of calling [`next_u64`] per element as [`Rng::gen`]
+    /// does. Whether this is faster depends on the generator's implementation
+    /// of [`fill_bytes`]; for most generators the cost of generation
+    /// dominates. The conversion
+    /// is the same as for [`Standard`], though generators whose
+    /// [`fill_bytes`] output differs from that of [`next_u64`] yield
+    /// different values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut samples = vec![0.0; 1000];
+    /// thread_rng().fill_uniform_f64(&mut samples);
+    /// assert!(samples.iter().all(|&x| 0.0 <= x && x < 1.0));
+    /// ```
+    ///
+    /// [`Standard`]: distributions::Standard
+    /// [`fill_bytes`]: RngCore::fill_bytes
+    /// [`next_u64`]: RngCore::next_u64
+    fn fill_uniform_f64(&mut self, dest: &mut [f64]) {
+        // As for `Standard`: use the 53 most significant bits
+        let scale = 1.0 / ((1u64 << 53) as f64);
+        let mut words = [0u64; 32];
+        for chunk in dest.chunks_mut(words.len()) {
+            let words = &mut words[..chunk.len()];
+            self.fill(words);
+            for (x, &w) in chunk.iter_mut().zip(words.iter()) {
+                *x = scale * (w >> 11) as f64;
+            }
+        }
+    }
+
+    /// Fill `dest` with random bytes having exactly `ones` bits set.
+    ///
+    /// The `ones` set bits are chosen uniformly among all `8 * dest.len()`
+    /// bit positions, using Floyd's combination algorithm; all other bits
+    /// are cleared. This takes one random index per set bit and needs no
+    /// allocation.
+    ///
+    /// # Panics
+    ///
+    /// If `ones > 8 * dest.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut key = [0u8; 16];
+    /// thread_rng().fill_bytes_weight(&mut key, 64);
+    /// assert_eq!(key.iter().map(|b| b.count_ones()).sum::<u32>(), 64);
+    /// ```
+    fn fill_bytes_weight(&mut self, dest: &mut [u8], ones: usize) {
+        let bits = dest.len() * 8;
+        assert!(ones <= bits, "fill_bytes_weight: ones exceeds the number of bits");
+        for byte in dest.iter_mut() {
+            *byte = 0;
+        }
+        // Floyd's algorithm, using `dest` itself as the set of chosen bits
+        for j in (bits - ones)..bits {
+            let t = crate::seq::gen_index(self, j + 1);
+            let pos = if dest[t / 8] & (1 << (t % 8)) != 0 { j } else { t };
+            dest[pos / 8] |= 1 << (pos % 8);
+        }
+    }
+
     /// Return a bool with a probability `p` of being true.
     ///
     /// See also the [`Bernoulli`] distribution, which may be faster if
@@ -300,130 +403,1683 @@ pub trait Rng: RngCore {
         let d = distributions::Bernoulli::from_ratio(numerator, denominator).unwrap();
         self.sample(d)
     }
-}
 
-impl<R: RngCore + ?Sized> Rng for R {}
-
-/// Types which may be filled with random data
-///
-/// This trait allows arrays to be efficiently filled with random data.
-///
-/// Implementations are expected to be portable across machines unless
-/// clearly documented otherwise (see the
-/// [Chapter on Portability](https://rust-random.github.io/book/portability.html)).
-pub trait Fill {
-    /// Fill self with random data
-    fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error>;
-}
+    /// Sample from the geometric distribution with success probability 1/2,
+    /// using a single `u64` from the generator.
+    ///
+    /// The result is the number of trailing zero bits of a random word, i.e.
+    /// the number of failures before the first success of a fair coin, and
+    /// equals `k` with probability `2^-(k+1)`. This is useful for example to
+    /// pick levels in a skip list.
+    ///
+    /// The result is capped at 64, which is returned (with probability
+    /// `2^-64`) when all bits are zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let level = rng.gen_geometric_p_half().min(15);
+    /// println!("{}", level);
+    /// ```
+    #[inline]
+    fn gen_geometric_p_half(&mut self) -> u32 {
+        self.next_u64().trailing_zeros()
+    }
 
-macro_rules! impl_fill_each {
-    () => {};
-    ($t:ty) => {
-        impl Fill for [$t] {
-            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
-                for elt in self.iter_mut() {
-                    *elt = rng.gen();
+    /// Simulate the coupon collector's problem: draw uniformly from `n`
+    /// distinct coupons until each has been seen at least once, and return
+    /// the number of draws taken.
+    ///
+    /// The expected result is `n * H_n`, where `H_n` is the `n`-th harmonic
+    /// number. Since only the count of distinct coupons seen matters, coupons
+    /// are relabelled so that those already collected are `0..k`; this
+    /// avoids allocating a set of seen coupons. Returns `0` when `n == 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let steps = thread_rng().coupon_collector_steps(10);
+    /// assert!(steps >= 10);
+    /// ```
+    fn coupon_collector_steps(&mut self, n: u32) -> u64 {
+        let mut steps = 0;
+        for collected in 0..n {
+            loop {
+                steps += 1;
+                if self.gen_range(0..n) >= collected {
+                    break;
                 }
-                Ok(())
             }
         }
-    };
-    ($t:ty, $($tt:ty,)*) => {
-        impl_fill_each!($t);
-        impl_fill_each!($($tt,)*);
-    };
-}
+        steps
+    }
 
-impl_fill_each!(bool, char, f32, f64,);
+    /// Return `+1` or `-1` with equal probability.
+    ///
+    /// This consumes a single random bit, as when sampling a `bool`. Any type
+    /// convertible from `i8` may be returned, including all signed integer
+    /// and floating-point types.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let step: i32 = rng.gen_sign();
+    /// let scale: f64 = rng.gen_sign();
+    /// println!("{} {}", step, scale);
+    /// ```
+    #[inline]
+    fn gen_sign<T: From<i8>>(&mut self) -> T {
+        if self.gen::<bool>() {
+            T::from(1)
+        } else {
+            T::from(-1)
+        }
+    }
 
-impl Fill for [u8] {
-    fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
-        rng.try_fill_bytes(self)
+    /// Generate a random `f64` uniformly in the open range
+    /// `(-magnitude, magnitude)`.
+    ///
+    /// This is equivalent to [`Rng::gen_open_range`] with symmetric bounds.
+    ///
+    /// # Panics
+    ///
+    /// If `magnitude` is not positive or not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let dx = rng.gen_signed_range(0.5);
+    /// assert!(-0.5 < dx && dx < 0.5);
+    /// ```
+    fn gen_signed_range(&mut self, magnitude: f64) -> f64 {
+        assert!(
+            magnitude > 0.0 && magnitude.is_finite(),
+            "gen_signed_range: magnitude is not positive and finite"
+        );
+        self.gen_open_range(-magnitude, magnitude)
     }
-}
 
-macro_rules! impl_fill {
-    () => {};
-    ($t:ty) => {
-        impl Fill for [$t] {
-            #[inline(never)] // in micro benchmarks, this improves performance
-            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
-                if self.len() > 0 {
-                    rng.try_fill_bytes(unsafe {
-                        slice::from_raw_parts_mut(self.as_mut_ptr()
-                            as *mut u8,
-                            self.len() * mem::size_of::<$t>()
-                        )
-                    })?;
-                    for x in self {
-                        *x = x.to_le();
-                    }
-                }
-                Ok(())
-            }
+    /// Remove a uniformly chosen element from `values` and return it, or
+    /// return `None` if `values` is empty.
+    ///
+    /// The element is removed with [`Vec::swap_remove`], hence this is `O(1)`
+    /// but does not preserve the order of the remaining elements. Calling this
+    /// method repeatedly drains the vector in a random order without any
+    /// further allocation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let mut deck: Vec<u32> = (1..=52).collect();
+    /// while let Some(card) = rng.swap_remove_random(&mut deck) {
+    ///     println!("{}", card);
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn swap_remove_random<T>(&mut self, values: &mut Vec<T>) -> Option<T> {
+        if values.is_empty() {
+            return None;
         }
+        let index = crate::seq::gen_index(self, values.len());
+        Some(values.swap_remove(index))
+    }
 
-        impl Fill for [Wrapping<$t>] {
-            #[inline(never)]
-            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
-                if self.len() > 0 {
-                    rng.try_fill_bytes(unsafe {
-                        slice::from_raw_parts_mut(self.as_mut_ptr()
-                            as *mut u8,
-                            self.len() * mem::size_of::<$t>()
-                        )
-                    })?;
-                    for x in self {
-                    *x = Wrapping(x.0.to_le());
-                    }
-                }
-                Ok(())
-            }
-        }
-    };
-    ($t:ty, $($tt:ty,)*) => {
-        impl_fill!($t);
-        // TODO: this could replace above impl once Rust #32463 is fixed
-        // impl_fill!(Wrapping<$t>);
-        impl_fill!($($tt,)*);
+    /// Choose one element at random from `iter`, in a single pass.
+    ///
+    /// Returns `None` if and only if the iterator is empty. This uses
+    /// reservoir sampling with a reservoir of size 1: the `i`-th element
+    /// (counting from 1) replaces the current choice with probability `1/i`.
+    /// Hence the iterator is always fully consumed, with one random sample
+    /// per element, but it need not have a known length.
+    ///
+    /// This is equivalent to [`IteratorRandom::choose_stable`]; see also
+    /// [`IteratorRandom::choose`], which may be faster for iterators with
+    /// accurate size hints.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let text = "the quick brown fox jumps over the lazy dog";
+    /// let word = rng.choose_from_iter(text.split_whitespace());
+    /// println!("{:?}", word);
+    /// ```
+    ///
+    /// [`IteratorRandom::choose`]: crate::seq::IteratorRandom::choose
+    /// [`IteratorRandom::choose_stable`]: crate::seq::IteratorRandom::choose_stable
+    fn choose_from_iter<I: Iterator>(&mut self, iter: I) -> Option<I::Item> {
+        crate::seq::IteratorRandom::choose_stable(iter, self)
     }
-}
 
-impl_fill!(u16, u32, u64, usize,);
-#[cfg(not(target_os = "emscripten"))]
-impl_fill!(u128);
-impl_fill!(i8, i16, i32, i64, isize,);
-#[cfg(not(target_os = "emscripten"))]
-impl_fill!(i128);
+    /// Choose an element of `values`, biased towards the most recent (last)
+    /// elements.
+    ///
+    /// The last element has the highest weight, and weights decay
+    /// geometrically by a factor of `decay` towards the front: the element
+    /// `k` positions before the last has weight `decay^k`. With `decay = 1`
+    /// all elements are equally likely. This is useful for example to pick
+    /// from a history buffer when simulating cache replacement.
+    ///
+    /// Returns `None` if `values` is empty.
+    ///
+    /// # Panics
+    ///
+    /// If `decay` is not in the range `(0, 1]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let history = ["a", "b", "c", "d"];
+    /// // "d" is twice as likely as "c", which is twice as likely as "b"...
+    /// let item = thread_rng().choose_recency_biased(&history, 0.5);
+    /// println!("{:?}", item);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn choose_recency_biased<'a, T>(&mut self, values: &'a [T], decay: f64) -> Option<&'a T> {
+        assert!(
+            decay > 0.0 && decay <= 1.0,
+            "choose_recency_biased: decay not in (0, 1]"
+        );
+        let n = values.len();
+        if n == 0 {
+            return None;
+        }
+        let k = if decay == 1.0 {
+            crate::seq::gen_index(self, n)
+        } else {
+            // Inverse CDF of the geometric distribution truncated to 0..n
+            let total = 1.0 - decay.powf(n as f64);
+            let u: f64 = self.gen();
+            let k = ((-u * total).ln_1p() / decay.ln()) as usize;
+            k.min(n - 1)
+        };
+        Some(&values[n - 1 - k])
+    }
 
-#[cfg(feature = "min_const_gen")]
-impl<T, const N: usize> Fill for [T; N]
-where [T]: Fill
-{
-    fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
-        self[..].try_fill(rng)
+    /// Choose an element of `values` uniformly among those not equal to any
+    /// element of `excluded`.
+    ///
+    /// Returns `None` if all elements are excluded (or `values` is empty).
+    /// The allowed elements are counted first and one of them is chosen with
+    /// a single random index, hence this does not loop even when most
+    /// elements are excluded; it takes `O(values.len() * excluded.len())`
+    /// comparisons.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let colors = ["red", "green", "blue", "yellow"];
+    /// let used = ["red", "blue"];
+    /// let color = thread_rng().choose_excluding(&colors, &used).unwrap();
+    /// assert!(*color == "green" || *color == "yellow");
+    /// ```
+    fn choose_excluding<'a, T: PartialEq>(&mut self, values: &'a [T], excluded: &[T]) -> Option<&'a T> {
+        let is_allowed = |x: &&T| !excluded.contains(x);
+        let count = values.iter().filter(is_allowed).count();
+        if count == 0 {
+            return None;
+        }
+        let index = crate::seq::gen_index(self, count);
+        values.iter().filter(is_allowed).nth(index)
     }
-}
 
-#[cfg(not(feature = "min_const_gen"))]
-macro_rules! impl_fill_arrays {
-    ($n:expr,) => {};
-    ($n:expr, $N:ident) => {
-        impl<T> Fill for [T; $n] where [T]: Fill {
-            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
-                self[..].try_fill(rng)
+    /// Choose an index in `0..len` with probability proportional to
+    /// `weight_fn(index)`.
+    ///
+    /// Unlike [`WeightedIndex`], weights are never stored: `weight_fn` is
+    /// called once per index to compute the total weight, then again while
+    /// walking the cumulative sum up to a uniformly sampled point. This suits
+    /// large virtual populations, but costs `O(len)` per sample; to sample
+    /// repeatedly from the same weights, prefer [`WeightedIndex`].
+    ///
+    /// Returns `None` if `len == 0` or all weights are zero. `weight_fn`
+    /// should return the same value each time it is called for an index.
+    ///
+    /// # Panics
+    ///
+    /// If any weight is negative or not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// // Index i has weight i, so index 0 is never chosen
+    /// let i = thread_rng().choose_weighted_fn(1_000_000, |i| i as f64).unwrap();
+    /// assert!(i > 0);
+    /// ```
+    ///
+    /// [`WeightedIndex`]: distributions::WeightedIndex
+    fn choose_weighted_fn<F: Fn(usize) -> f64>(&mut self, len: usize, weight_fn: F) -> Option<usize> {
+        let mut total = 0.0;
+        for i in 0..len {
+            let w = weight_fn(i);
+            assert!(
+                w >= 0.0 && w.is_finite(),
+                "choose_weighted_fn: weight is negative or not finite"
+            );
+            total += w;
+        }
+        if total == 0.0 {
+            return None;
+        }
+
+        let target = self.gen::<f64>() * total;
+        let mut cumulative = 0.0;
+        let mut last = 0;
+        for i in 0..len {
+            let w = weight_fn(i);
+            if w > 0.0 {
+                cumulative += w;
+                last = i;
+                if target < cumulative {
+                    return Some(i);
+                }
             }
         }
+        // Only reachable due to rounding in the cumulative sum
+        Some(last)
+    }
+
+    /// Resample `size` elements of `data` uniformly with replacement, as
+    /// used for bootstrapping.
+    ///
+    /// References are returned, so repeated elements are cheap; each element
+    /// of the result is independently and uniformly chosen from `data`.
+    /// Returns an empty vector if `data` is empty. See
+    /// [`SliceRandom::choose_multiple`] for sampling without replacement.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let data = [2.5, 3.0, 4.5, 1.0];
+    /// let resample = thread_rng().bootstrap(&data, 10);
+    /// let mean = resample.iter().copied().sum::<f64>() / 10.0;
+    /// assert!((1.0..=4.5).contains(&mean));
+    /// ```
+    ///
+    /// [`SliceRandom::choose_multiple`]: crate::seq::SliceRandom::choose_multiple
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn bootstrap<'a, T>(&mut self, data: &'a [T], size: usize) -> Vec<&'a T> {
+        if data.is_empty() {
+            return Vec::new();
+        }
+        (0..size)
+            .map(|_| &data[crate::seq::gen_index(self, data.len())])
+            .collect()
+    }
+
+    /// Generate a `rows × cols` matrix of values sampled from `distr`, as a
+    /// vector of rows.
+    ///
+    /// Elements are sampled in row-major order. See
+    /// [`Rng::gen_matrix_flat`] for a variant using a single allocation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    /// use rand::distributions::Uniform;
+    ///
+    /// let mut rng = thread_rng();
+    /// let m = rng.gen_matrix(2, 3, &Uniform::new(-1.0, 1.0));
+    /// assert_eq!(m.len(), 2);
+    /// assert_eq!(m[0].len(), 3);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn gen_matrix<D>(&mut self, rows: usize, cols: usize, distr: &D) -> Vec<Vec<f64>>
+    where
+        D: Distribution<f64>,
+    {
+        (0..rows)
+            .map(|_| (0..cols).map(|_| distr.sample(self)).collect())
+            .collect()
+    }
+
+    /// Generate a `rows × cols` matrix of values sampled from `distr`, as a
+    /// single vector in row-major order.
+    ///
+    /// The element at row `i` and column `j` is at index `i * cols + j`.
+    /// This yields the same values as [`Rng::gen_matrix`] given the same
+    /// generator state, but requires only one allocation.
+    ///
+    /// # Panics
+    ///
+    /// If `rows * cols` overflows a `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    /// use rand::distributions::Uniform;
+    ///
+    /// let mut rng = thread_rng();
+    /// let m = rng.gen_matrix_flat(2, 3, &Uniform::new(-1.0, 1.0));
+    /// assert_eq!(m.len(), 6);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn gen_matrix_flat<D>(&mut self, rows: usize, cols: usize, distr: &D) -> Vec<f64>
+    where
+        D: Distribution<f64>,
+    {
+        let len = rows.checked_mul(cols).expect("gen_matrix_flat: rows * cols overflows");
+        (0..len).map(|_| distr.sample(self)).collect()
+    }
+
+    /// Generate a uniformly random permutation of `0..n` by sampling its
+    /// index (rank) in lexicographic order.
+    ///
+    /// A single integer is sampled uniformly from `[0, n!)` and decoded via
+    /// its Lehmer code, i.e. its digits in the factorial number system: for
+    /// position `i`, digit `d_i` selects the `d_i`-th smallest of the values
+    /// not yet used. The permutation with index 0 is the identity. Unlike
+    /// [`SliceRandom::shuffle`], this consumes a single sample, and the rank
+    /// may be recovered from the result.
+    ///
+    /// # Panics
+    ///
+    /// If `n > 20`, since `21!` does not fit in a `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let perm = rng.gen_permutation_index(5);
+    /// let mut sorted = perm.clone();
+    /// sorted.sort();
+    /// assert_eq!(sorted, [0, 1, 2, 3, 4]);
+    /// ```
+    ///
+    /// [`SliceRandom::shuffle`]: crate::seq::SliceRandom::shuffle
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn gen_permutation_index(&mut self, n: usize) -> Vec<u32> {
+        assert!(n <= 20, "gen_permutation_index: n > 20");
+        let factorial = (1..=n as u64).product::<u64>();
+        let mut rank = self.gen_range(0..factorial);
+
+        let mut available: Vec<u32> = (0..n as u32).collect();
+        let mut permutation = Vec::with_capacity(n);
+        let mut radix = factorial;
+        for i in 0..n {
+            // `radix` is `(n - i)!`; the next digit has weight `(n - i - 1)!`
+            radix /= (n - i) as u64;
+            let digit = (rank / radix) as usize;
+            rank %= radix;
+            permutation.push(available.remove(digit));
+        }
+        permutation
+    }
+
+    /// Generate a uniformly random permutation of `0..n` with exactly `k`
+    /// fixed points, i.e. with `perm[i] == i` for exactly `k` indices.
+    ///
+    /// The fixed points are a uniformly random `k`-subset, and the remaining
+    /// `n - k` elements form a uniformly random derangement, so every
+    /// permutation with exactly `k` fixed points is equally likely. With
+    /// `k == 0` this samples a derangement. The derangement is sampled by
+    /// Fisher-Yates shuffling, restarting as soon as an element lands in its
+    /// own position; this takes about `e` attempts on average.
+    ///
+    /// # Panics
+    ///
+    /// If `k > n`, or if `n - k == 1` (a single element cannot be deranged).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let perm = thread_rng().gen_permutation_fixed_points(6, 0);
+    /// assert!(perm.iter().enumerate().all(|(i, &x)| x as usize != i));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn gen_permutation_fixed_points(&mut self, n: usize, k: usize) -> Vec<u32> {
+        assert!(k <= n, "gen_permutation_fixed_points: k > n");
+        assert!(n - k != 1, "gen_permutation_fixed_points: n - k == 1");
+        let fixed = self.gen_combination(n, k);
+        let free: Vec<usize> = (0..n).filter(|i| fixed.binary_search(i).is_err()).collect();
+
+        let m = free.len();
+        let mut derangement: Vec<usize> = Vec::with_capacity(m);
+        'retry: loop {
+            derangement.clear();
+            derangement.extend(0..m);
+            for i in (1..m).rev() {
+                derangement.swap(i, crate::seq::gen_index(self, i + 1));
+                if derangement[i] == i {
+                    continue 'retry;
+                }
+            }
+            if m == 0 || derangement[0] != 0 {
+                break;
+            }
+        }
+
+        let mut permutation: Vec<u32> = (0..n as u32).collect();
+        for (&pos, &j) in free.iter().zip(derangement.iter()) {
+            permutation[pos] = free[j] as u32;
+        }
+        permutation
+    }
+
+    /// Sample a uniformly random `k`-subset of `0..n` as sorted indices.
+    ///
+    /// This uses Floyd's combination algorithm, which makes exactly `k` calls
+    /// to the generator, and every `k`-subset is equally likely. Indices are
+    /// inserted in sorted position as they are chosen, hence the cost is
+    /// `O(k^2)` in the worst case, independent of `n`. If `k >= n`, all of
+    /// `0..n` is returned.
+    ///
+    /// See also [`seq::index::sample`], which returns indices in random
+    /// order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let picks = rng.gen_combination(49, 6);
+    /// assert_eq!(picks.len(), 6);
+    /// assert!(picks.windows(2).all(|w| w[0] < w[1]));
+    /// ```
+    ///
+    /// [`seq::index::sample`]: crate::seq::index::sample
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn gen_combination(&mut self, n: usize, k: usize) -> Vec<usize> {
+        if k >= n {
+            return (0..n).collect();
+        }
+        let mut combination = Vec::with_capacity(k);
+        for j in n - k..n {
+            let t = crate::seq::gen_index(self, j + 1);
+            // If `t` was already chosen then `j` cannot have been, since all
+            // previous values are less than `j`; `j` goes last.
+            match combination.binary_search(&t) {
+                Ok(_) => combination.push(j),
+                Err(pos) => combination.insert(pos, t),
+            }
+        }
+        combination
+    }
+
+    /// Generate a uniformly random labelled tree on the nodes `0..n`, as a
+    /// list of edges.
+    ///
+    /// Each of the `n^(n-2)` labelled trees is equally likely. The tree is
+    /// obtained by decoding a uniformly random Prüfer sequence, in `O(n)`
+    /// time. The result has `n - 1` edges (none for `n <= 1`) and is
+    /// connected and acyclic; the two nodes of each edge are in no particular
+    /// order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let edges = thread_rng().gen_random_tree(10);
+    /// assert_eq!(edges.len(), 9);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn gen_random_tree(&mut self, n: usize) -> Vec<(usize, usize)> {
+        if n <= 1 {
+            return Vec::new();
+        }
+        let sequence: Vec<usize> = (0..n - 2).map(|_| crate::seq::gen_index(self, n)).collect();
+        let mut degree = alloc::vec![1usize; n];
+        for &v in sequence.iter() {
+            degree[v] += 1;
+        }
+
+        // Repeatedly join the smallest leaf to the next node of the sequence
+        let mut edges = Vec::with_capacity(n - 1);
+        let mut ptr = degree.iter().position(|&d| d == 1).unwrap();
+        let mut leaf = ptr;
+        for &v in sequence.iter() {
+            edges.push((leaf, v));
+            degree[v] -= 1;
+            if degree[v] == 1 && v < ptr {
+                leaf = v;
+            } else {
+                ptr += 1;
+                while degree[ptr] != 1 {
+                    ptr += 1;
+                }
+                leaf = ptr;
+            }
+        }
+        edges.push((leaf, n - 1));
+        edges
+    }
+
+    /// Rotate `values` left by a uniformly chosen offset in `0..len`.
+    ///
+    /// Unlike [`SliceRandom::shuffle`], this preserves the cyclic order of
+    /// the elements; only the starting element is random. Each of the `len`
+    /// rotations is equally likely. Empty slices are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let mut players = ["alice", "bob", "carol"];
+    /// rng.rotate_random(&mut players);
+    /// println!("{:?} starts", players[0]);
+    /// ```
+    ///
+    /// [`SliceRandom::shuffle`]: crate::seq::SliceRandom::shuffle
+    fn rotate_random<T>(&mut self, values: &mut [T]) {
+        if values.is_empty() {
+            return;
+        }
+        let offset = crate::seq::gen_index(self, values.len());
+        values.rotate_left(offset);
+    }
+
+    /// Shuffle `values` in place, leaving the elements at pinned positions
+    /// untouched.
+    ///
+    /// Position `i` is pinned if `pinned[i]` is `true`. The elements at
+    /// unpinned positions are permuted uniformly at random among those
+    /// positions, using a Fisher-Yates shuffle as in
+    /// [`SliceRandom::shuffle`].
+    ///
+    /// # Panics
+    ///
+    /// If `values` and `pinned` differ in length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut slots = ["standup", "a", "b", "c", "lunch", "d"];
+    /// let pinned = [true, false, false, false, true, false];
+    /// thread_rng().shuffle_with_pinned(&mut slots, &pinned);
+    /// assert_eq!(slots[0], "standup");
+    /// assert_eq!(slots[4], "lunch");
+    /// ```
+    ///
+    /// [`SliceRandom::shuffle`]: crate::seq::SliceRandom::shuffle
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn shuffle_with_pinned<T>(&mut self, values: &mut [T], pinned: &[bool]) {
+        assert_eq!(
+            values.len(),
+            pinned.len(),
+            "shuffle_with_pinned: values and pinned differ in length"
+        );
+        let free: Vec<usize> = (0..values.len()).filter(|&i| !pinned[i]).collect();
+        for i in (1..free.len()).rev() {
+            let j = crate::seq::gen_index(self, i + 1);
+            values.swap(free[i], free[j]);
+        }
+    }
+
+    /// Generate a random `f64` in the half-open range `[low, high)`, shaped
+    /// by a power curve.
+    ///
+    /// The result is `low + (high - low) * u.powf(exponent)` where `u` is
+    /// sampled from the [`Standard`] distribution. An `exponent` greater than
+    /// 1 biases results towards `low`, an `exponent` less than 1 biases
+    /// results towards `high` and an `exponent` of exactly 1 yields a uniform
+    /// distribution.
+    ///
+    /// # Panics
+    ///
+    /// If `low >= high` or `exponent <= 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// // Mostly easy levels, with the occasional hard one
+    /// let difficulty = rng.gen_range_shaped(1.0, 10.0, 3.0);
+    /// println!("{}", difficulty);
+    /// ```
+    ///
+    /// [`Standard`]: distributions::Standard
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn gen_range_shaped(&mut self, low: f64, high: f64, exponent: f64) -> f64 {
+        assert!(low < high, "gen_range_shaped: low >= high");
+        assert!(exponent > 0.0, "gen_range_shaped: exponent <= 0");
+        let u: f64 = self.gen();
+        low + (high - low) * u.powf(exponent)
+    }
+
+    /// Generate one of `levels` evenly spaced values in `[low, high]`.
+    ///
+    /// An index `i` is sampled uniformly from `0..levels` and mapped
+    /// linearly to `low * (1 - t) + high * t` where `t = i / (levels - 1)`,
+    /// hence both `low` and `high` are reachable exactly. If `levels == 1`,
+    /// `low` is always returned. Since only the integer index is random, the
+    /// result for a given generator state does not depend on the rounding
+    /// behaviour of floating-point sampling.
+    ///
+    /// # Panics
+    ///
+    /// If `levels == 0` or `low >= high`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// // One of 0.0, 0.25, 0.5, 0.75 or 1.0
+    /// let x = rng.gen_quantized_f64(0.0, 1.0, 5);
+    /// assert_eq!((x * 4.0).fract(), 0.0);
+    /// ```
+    fn gen_quantized_f64(&mut self, low: f64, high: f64, levels: u64) -> f64 {
+        assert!(levels > 0, "gen_quantized_f64: levels == 0");
+        assert!(low < high, "gen_quantized_f64: low >= high");
+        if levels == 1 {
+            return low;
+        }
+        let t = self.gen_range(0..levels) as f64 / (levels - 1) as f64;
+        low * (1.0 - t) + high * t
+    }
+
+    /// Perturb `value` by Gaussian noise with standard deviation `std_dev`.
+    ///
+    /// Returns `value + std_dev * z` where `z` is sampled from the standard
+    /// normal distribution. For more control over normal sampling, see the
+    /// `Normal` distribution in the [`rand_distr`] crate.
+    ///
+    /// # Panics
+    ///
+    /// If `std_dev` is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let temperature = rng.jitter(20.0, 0.5);
+    /// println!("{}", temperature);
+    /// ```
+    ///
+    /// [`rand_distr`]: https://crates.io/crates/rand_distr
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn jitter(&mut self, value: f64, std_dev: f64) -> f64 {
+        assert!(std_dev >= 0.0, "jitter: std_dev < 0 or NaN");
+        value + std_dev * distributions::utils::sample_standard_normal(self)
+    }
+
+    /// Generate a pair of standard normal values with correlation `rho`.
+    ///
+    /// Two independent standard normal values `z1` and `z2` are sampled, and
+    /// the pair `(z1, rho * z1 + sqrt(1 - rho^2) * z2)` is returned. Both
+    /// values have mean 0 and variance 1, and their Pearson correlation is
+    /// `rho`. Scale and shift the results for other means and variances.
+    ///
+    /// # Panics
+    ///
+    /// If `rho` is not in `[-1, 1]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let (x, y) = rng.gen_correlated_normal(0.8);
+    /// println!("({}, {})", x, y);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn gen_correlated_normal(&mut self, rho: f64) -> (f64, f64) {
+        assert!(
+            (-1.0..=1.0).contains(&rho),
+            "gen_correlated_normal: rho not in [-1, 1]"
+        );
+        let z1 = distributions::utils::sample_standard_normal(self);
+        let z2 = distributions::utils::sample_standard_normal(self);
+        (z1, rho * z1 + (1.0 - rho * rho).sqrt() * z2)
+    }
+
+    /// Randomly perturb the probability vector `probs` in place, keeping it
+    /// a valid probability vector.
+    ///
+    /// The result is a sample from the Dirichlet distribution with
+    /// parameters `concentration * probs[i]`, whose mean is `probs`. Higher
+    /// `concentration` yields smaller perturbations; the variance of entry `i`
+    /// is `probs[i] * (1 - probs[i]) / (concentration + 1)`. The result is
+    /// non-negative and sums to 1 (up to rounding). Entries which are zero
+    /// remain zero.
+    ///
+    /// # Panics
+    ///
+    /// If `concentration` is not positive and finite, if any entry of `probs`
+    /// is negative or not finite, or if all entries are zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut policy = [0.5, 0.3, 0.2];
+    /// thread_rng().perturb_simplex(&mut policy, 100.0);
+    /// assert!((policy.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn perturb_simplex(&mut self, probs: &mut [f64], concentration: f64) {
+        assert!(
+            concentration > 0.0 && concentration.is_finite(),
+            "perturb_simplex: concentration is not positive and finite"
+        );
+        assert!(
+            probs.iter().all(|&p| p >= 0.0 && p.is_finite()) && probs.iter().any(|&p| p > 0.0),
+            "perturb_simplex: invalid probabilities"
+        );
+        // Sample Gamma variates in log-space and normalize relative to the
+        // largest, which avoids underflow for small parameters.
+        let mut max = ::core::f64::NEG_INFINITY;
+        for p in probs.iter_mut() {
+            if *p > 0.0 {
+                *p = distributions::utils::sample_ln_gamma(self, concentration * *p);
+                max = max.max(*p);
+            } else {
+                *p = ::core::f64::NEG_INFINITY;
+            }
+        }
+        let mut sum = 0.0;
+        for p in probs.iter_mut() {
+            *p = (*p - max).exp();
+            sum += *p;
+        }
+        for p in probs.iter_mut() {
+            *p /= sum;
+        }
+    }
+
+    /// Generate a Brownian motion (Wiener process) path with `steps` steps of
+    /// duration `dt`.
+    ///
+    /// The result has `steps + 1` points, starting at 0; each further point
+    /// adds an independent `Normal(0, volatility * sqrt(dt))` increment to
+    /// the previous one. Hence point `k` has variance
+    /// `k * volatility^2 * dt`.
+    ///
+    /// # Panics
+    ///
+    /// If `dt` or `volatility` is negative or not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let path = thread_rng().gen_brownian_path(100, 0.01, 2.0);
+    /// assert_eq!(path.len(), 101);
+    /// assert_eq!(path[0], 0.0);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn gen_brownian_path(&mut self, steps: usize, dt: f64, volatility: f64) -> Vec<f64> {
+        assert!(
+            dt >= 0.0 && dt.is_finite(),
+            "gen_brownian_path: dt is negative or not finite"
+        );
+        assert!(
+            volatility >= 0.0 && volatility.is_finite(),
+            "gen_brownian_path: volatility is negative or not finite"
+        );
+        let std_dev = volatility * dt.sqrt();
+        let mut path = Vec::with_capacity(steps + 1);
+        let mut x = 0.0;
+        path.push(x);
+        for _ in 0..steps {
+            x += std_dev * distributions::utils::sample_standard_normal(self);
+            path.push(x);
+        }
+        path
+    }
+
+    /// Generate `n` consecutive values of a stationary AR(1) process.
+    ///
+    /// Each value is `x[t] = phi * x[t-1] + e[t]`, where the innovations
+    /// `e[t]` are independent `Normal(0, sigma)` samples. The first value is
+    /// drawn from the stationary distribution `Normal(0, sigma / sqrt(1 -
+    /// phi^2))`, so there is no burn-in: every value has this distribution,
+    /// and the lag-`k` autocorrelation is `phi^k`.
+    ///
+    /// # Panics
+    ///
+    /// If `|phi| >= 1` (the process is not stationary), or if `sigma` is
+    /// negative or not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let series = thread_rng().gen_ar1(100, 0.9, 1.0);
+    /// assert_eq!(series.len(), 100);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn gen_ar1(&mut self, n: usize, phi: f64, sigma: f64) -> Vec<f64> {
+        assert!(phi.abs() < 1.0, "gen_ar1: |phi| >= 1 or NaN");
+        assert!(
+            sigma >= 0.0 && sigma.is_finite(),
+            "gen_ar1: sigma is negative or not finite"
+        );
+        let mut series = Vec::with_capacity(n);
+        if n == 0 {
+            return series;
+        }
+        let stationary_std_dev = sigma / (1.0 - phi * phi).sqrt();
+        let mut x = stationary_std_dev * distributions::utils::sample_standard_normal(self);
+        series.push(x);
+        for _ in 1..n {
+            x = phi * x + sigma * distributions::utils::sample_standard_normal(self);
+            series.push(x);
+        }
+        series
+    }
+
+    /// Generate a uniformly random integer lattice point `(x, y)` inside the
+    /// disc of the given `radius` centered at the origin, i.e. with
+    /// `x^2 + y^2 <= radius^2`.
+    ///
+    /// This uses rejection sampling over the square `[-radius, radius]^2`,
+    /// which accepts about 79% of candidates.
+    ///
+    /// # Panics
+    ///
+    /// If `radius < 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let (x, y) = thread_rng().gen_lattice_in_disc(10);
+    /// assert!(x * x + y * y <= 100);
+    /// ```
+    fn gen_lattice_in_disc(&mut self, radius: i32) -> (i32, i32) {
+        assert!(radius >= 0, "gen_lattice_in_disc: radius < 0");
+        let r2 = i64::from(radius) * i64::from(radius);
+        loop {
+            let x = self.gen_range(-radius..=radius);
+            let y = self.gen_range(-radius..=radius);
+            if i64::from(x) * i64::from(x) + i64::from(y) * i64::from(y) <= r2 {
+                return (x, y);
+            }
+        }
+    }
+
+    /// Generate labelled 2D points from a mixture of Gaussian blobs, for
+    /// example to test clustering algorithms.
+    ///
+    /// For each center, `points_per` points are generated by adding
+    /// independent `Normal(0, std_dev)` noise to both coordinates, and
+    /// tagged with the index of the center. Points are ordered by cluster;
+    /// shuffle the result if a random order is required.
+    ///
+    /// # Panics
+    ///
+    /// If `std_dev` is negative or not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let centers = [[0.0, 0.0], [10.0, 0.0], [5.0, 8.0]];
+    /// let points = thread_rng().gen_gaussian_blobs(&centers, 1.0, 50);
+    /// assert_eq!(points.len(), 150);
+    /// assert_eq!(points[50].1, 1);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn gen_gaussian_blobs(
+        &mut self, centers: &[[f64; 2]], std_dev: f64, points_per: usize,
+    ) -> Vec<([f64; 2], usize)> {
+        assert!(
+            std_dev >= 0.0 && std_dev.is_finite(),
+            "gen_gaussian_blobs: std_dev is negative or not finite"
+        );
+        let mut points = Vec::with_capacity(centers.len() * points_per);
+        for (label, center) in centers.iter().enumerate() {
+            for _ in 0..points_per {
+                let x = center[0] + std_dev * distributions::utils::sample_standard_normal(self);
+                let y = center[1] + std_dev * distributions::utils::sample_standard_normal(self);
+                points.push(([x, y], label));
+            }
+        }
+        points
+    }
+
+    /// Generate stratified (jittered grid) samples in the unit square.
+    ///
+    /// The unit square is divided into a `grid_x × grid_y` grid of equal
+    /// cells, and one point is placed uniformly at random within each cell.
+    /// Compared to independent uniform points this covers the square more
+    /// evenly, which reduces the variance of Monte Carlo integration.
+    ///
+    /// Points are returned in row-major order: the point in column `i` and
+    /// row `j` is at index `j * grid_x + i` and lies in
+    /// `[i / grid_x, (i + 1) / grid_x) × [j / grid_y, (j + 1) / grid_y)`.
+    /// If either dimension is zero the result is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// // Estimate the integral of x * y over the unit square (1/4)
+    /// let points = thread_rng().gen_stratified_2d(16, 16);
+    /// let estimate = points.iter().map(|p| p[0] * p[1]).sum::<f64>() / 256.0;
+    /// assert!((estimate - 0.25).abs() < 0.01);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn gen_stratified_2d(&mut self, grid_x: usize, grid_y: usize) -> Vec<[f64; 2]> {
+        let mut points = Vec::with_capacity(grid_x * grid_y);
+        for j in 0..grid_y {
+            for i in 0..grid_x {
+                let u: f64 = self.gen();
+                let v: f64 = self.gen();
+                points.push([(i as f64 + u) / grid_x as f64, (j as f64 + v) / grid_y as f64]);
+            }
+        }
+        points
+    }
+
+    /// Perturb `value` by Gaussian noise, like [`Rng::jitter`], keeping the
+    /// result within `[low, high]`.
+    ///
+    /// Results falling outside of the bounds are reflected back into the
+    /// interval, as if the noise bounced off the bounds. This keeps the
+    /// density continuous within the interval and never needs to resample.
+    ///
+    /// # Panics
+    ///
+    /// If `std_dev` is negative or NaN, or if `low > high`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let p = rng.jitter_clamped(0.95, 0.1, 0.0, 1.0);
+    /// assert!(0.0 <= p && p <= 1.0);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn jitter_clamped(&mut self, value: f64, std_dev: f64, low: f64, high: f64) -> f64 {
+        assert!(low <= high, "jitter_clamped: low > high");
+        let x = self.jitter(value, std_dev);
+        let width = high - low;
+        if x >= low && x <= high {
+            return x;
+        }
+        if !(width > 0.0) {
+            return low;
+        }
+        // Reflect repeatedly by folding into a period of `2 * width`
+        let period = 2.0 * width;
+        let mut offset = (x - low) % period;
+        if offset < 0.0 {
+            offset += period;
+        }
+        if offset > width {
+            offset = period - offset;
+        }
+        // Guard against rounding errors
+        (low + offset).max(low).min(high)
+    }
+
+    /// Generate a random `f64` near `target`, from the symmetric triangular
+    /// distribution on `[target - spread, target + spread]`.
+    ///
+    /// The density peaks at `target` and falls linearly to zero at both
+    /// bounds. Samples are computed as `target + spread * (u1 - u2)` with
+    /// `u1` and `u2` sampled from [`Standard`]. For asymmetric triangular
+    /// distributions, see `Triangular` in the [`rand_distr`] crate.
+    ///
+    /// # Panics
+    ///
+    /// If `spread` is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let delay_ms = rng.gen_near(100.0, 20.0);
+    /// assert!(80.0 <= delay_ms && delay_ms <= 120.0);
+    /// ```
+    ///
+    /// [`Standard`]: distributions::Standard
+    /// [`rand_distr`]: https://crates.io/crates/rand_distr
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn gen_near(&mut self, target: f64, spread: f64) -> f64 {
+        assert!(spread >= 0.0, "gen_near: spread < 0 or NaN");
+        let u1: f64 = self.gen();
+        let u2: f64 = self.gen();
+        target + spread * (u1 - u2)
+    }
+
+    /// Generate a random color as `[red, green, blue]`, with each channel
+    /// uniformly distributed.
+    ///
+    /// This is equivalent to `self.gen::<[u8; 3]>()`. See
+    /// [`Rng::gen_hsv`] for more control over the generated colors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let [r, g, b] = rng.gen_rgb();
+    /// println!("#{:02x}{:02x}{:02x}", r, g, b);
+    /// ```
+    #[inline]
+    fn gen_rgb(&mut self) -> [u8; 3] {
+        self.gen()
+    }
+
+    /// Generate a random color as `[hue, saturation, value]`.
+    ///
+    /// The hue is uniformly distributed over `[0, 360)` degrees, while
+    /// saturation and value are uniformly distributed over the closed ranges
+    /// given as `(min, max)`. Restricting these avoids the dull, muddy colors
+    /// common when sampling RGB channels independently; for example, a
+    /// `s_range` of `(0.6, 0.9)` and `v_range` of `(0.8, 1.0)` yields vivid
+    /// colors.
+    ///
+    /// # Panics
+    ///
+    /// If either range does not satisfy `0 <= min <= max <= 1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let [h, s, v] = rng.gen_hsv((0.6, 0.9), (0.8, 1.0));
+    /// assert!(0.0 <= h && h < 360.0);
+    /// assert!(0.6 <= s && s <= 0.9);
+    /// ```
+    fn gen_hsv(&mut self, s_range: (f32, f32), v_range: (f32, f32)) -> [f32; 3] {
+        assert!(
+            0.0 <= s_range.0 && s_range.0 <= s_range.1 && s_range.1 <= 1.0,
+            "gen_hsv: invalid saturation range"
+        );
+        assert!(
+            0.0 <= v_range.0 && v_range.0 <= v_range.1 && v_range.1 <= 1.0,
+            "gen_hsv: invalid value range"
+        );
+        let h = self.gen_range(0.0..360.0);
+        let s = self.gen_range(s_range.0..=s_range.1);
+        let v = self.gen_range(v_range.0..=v_range.1);
+        [h, s, v]
+    }
+
+    /// Generate a random IPv4 address within the CIDR block
+    /// `base/prefix_len`.
+    ///
+    /// The first `prefix_len` bits of the result are taken from `base` and
+    /// the remaining host bits are uniformly random. Any host bits set in
+    /// `base` are ignored.
+    ///
+    /// # Panics
+    ///
+    /// If `prefix_len > 32`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let mut rng = thread_rng();
+    /// let addr = rng.gen_ip_in_cidr(Ipv4Addr::new(192, 168, 0, 0), 16);
+    /// assert_eq!(addr.octets()[..2], [192, 168]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn gen_ip_in_cidr(&mut self, base: std::net::Ipv4Addr, prefix_len: u8) -> std::net::Ipv4Addr {
+        assert!(prefix_len <= 32, "gen_ip_in_cidr: prefix_len > 32");
+        // Shifting a u32 by 32 overflows, hence use a u64
+        let host_mask = (::core::u32::MAX as u64 >> prefix_len) as u32;
+        let host: u32 = self.gen();
+        std::net::Ipv4Addr::from((u32::from(base) & !host_mask) | (host & host_mask))
+    }
+
+    /// Generate a random string of `digits` decimal digits which passes the
+    /// Luhn checksum, e.g. for test card numbers.
+    ///
+    /// The first `digits - 1` digits are uniformly random (so the result may
+    /// start with `0`) and the last digit is the Luhn check digit.
+    ///
+    /// # Panics
+    ///
+    /// If `digits == 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let card = rng.gen_luhn_number(16);
+    /// assert_eq!(card.len(), 16);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn gen_luhn_number(&mut self, digits: usize) -> String {
+        assert!(digits > 0, "gen_luhn_number: digits == 0");
+        let payload: Vec<u8> = (1..digits).map(|_| self.gen_range(0..10)).collect();
+        // Double every second digit, starting with the one left of the check
+        // digit
+        let sum: u32 = payload
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &d)| {
+                let d = u32::from(d);
+                match (i % 2 == 0, d * 2) {
+                    (false, _) => d,
+                    (true, doubled) if doubled > 9 => doubled - 9,
+                    (true, doubled) => doubled,
+                }
+            })
+            .sum();
+        let check = ((10 - sum % 10) % 10) as u8;
+        payload
+            .iter()
+            .chain(core::iter::once(&check))
+            .map(|&d| char::from(b'0' + d))
+            .collect()
+    }
+
+    /// Generate a random valid [semantic version](https://semver.org) string.
+    ///
+    /// The result has the form `major.minor.patch` with `major < 10`,
+    /// `minor < 20` and `patch < 100`. With probability 1/4 a pre-release
+    /// suffix such as `-alpha` or `-rc.3` is appended, and with probability
+    /// 1/8 build metadata such as `+build.42` or `+3f9a0c2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let version = thread_rng().gen_semver();
+    /// assert!(version.split('.').count() >= 3);
+    /// println!("{}", version);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn gen_semver(&mut self) -> String {
+        use core::fmt::Write;
+        const PRE_RELEASE: [&str; 4] = ["alpha", "beta", "rc", "pre"];
+        const HEX: &[u8] = b"0123456789abcdef";
+
+        let mut version = String::new();
+        let (major, minor, patch): (u32, u32, u32) =
+            (self.gen_range(0..10), self.gen_range(0..20), self.gen_range(0..100));
+        write!(version, "{}.{}.{}", major, minor, patch).unwrap();
+        if self.gen_ratio(1, 4) {
+            version.push('-');
+            version.push_str(PRE_RELEASE[self.gen_range(0..PRE_RELEASE.len())]);
+            if self.gen() {
+                write!(version, ".{}", self.gen_range(0..10)).unwrap();
+            }
+        }
+        if self.gen_ratio(1, 8) {
+            if self.gen() {
+                write!(version, "+build.{}", self.gen_range(0..1000)).unwrap();
+            } else {
+                version.push('+');
+                for _ in 0..7 {
+                    version.push(char::from(HEX[self.gen_range(0..HEX.len())]));
+                }
+            }
+        }
+        version
+    }
+
+    /// Return a uniformly random subset of the bits set in `mask`.
+    ///
+    /// Each set bit of `mask` is included independently with probability
+    /// 0.5, computed as `next_u64() & mask`. See
+    /// [`Rng::gen_bits_in_mask_weighted`] for other probabilities.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// const READ: u64 = 1;
+    /// const WRITE: u64 = 2;
+    /// const EXEC: u64 = 8;
+    /// let flags = thread_rng().gen_bits_in_mask(READ | WRITE | EXEC);
+    /// assert_eq!(flags & !(READ | WRITE | EXEC), 0);
+    /// ```
+    #[inline]
+    fn gen_bits_in_mask(&mut self, mask: u64) -> u64 {
+        self.next_u64() & mask
+    }
+
+    /// Return a random subset of the bits set in `mask`, including each set
+    /// bit independently with probability `p`.
+    ///
+    /// This samples a [`Bernoulli`] distribution once per set bit of `mask`.
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0` or `p > 1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let flags = thread_rng().gen_bits_in_mask_weighted(0xff00, 0.1);
+    /// assert_eq!(flags & !0xff00, 0);
+    /// ```
+    ///
+    /// [`Bernoulli`]: distributions::Bernoulli
+    fn gen_bits_in_mask_weighted(&mut self, mask: u64, p: f64) -> u64 {
+        let d = match distributions::Bernoulli::new(p) {
+            Ok(d) => d,
+            Err(_) => panic!("gen_bits_in_mask_weighted: p={:?} is outside range [0.0, 1.0]", p),
+        };
+        let mut bits = mask;
+        let mut result = 0;
+        while bits != 0 {
+            // Isolate the lowest set bit
+            let bit = bits & bits.wrapping_neg();
+            if self.sample(d) {
+                result |= bit;
+            }
+            bits &= !bit;
+        }
+        result
+    }
+
+    /// Generate an enum discriminant for fuzzing, which is usually valid but
+    /// sometimes out of range.
+    ///
+    /// With probability `1 - invalid_prob`, a valid discriminant is sampled
+    /// uniformly from `0..count`. Otherwise an invalid value outside
+    /// `0..count` is returned: half of the time one of the adjacent values
+    /// `-1` or `count`, which are most likely to expose off-by-one errors,
+    /// and otherwise a uniformly random invalid `i32`.
+    ///
+    /// # Panics
+    ///
+    /// If `count == 0`, `count > 2^31` or `invalid_prob` is not in `[0, 1]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// // Mostly one of the 4 valid values of a C enum
+    /// let d = thread_rng().gen_enum_discriminant(4, 0.05);
+    /// println!("{}", d);
+    /// ```
+    fn gen_enum_discriminant(&mut self, count: u32, invalid_prob: f64) -> i32 {
+        assert!(
+            count > 0 && count <= 1 << 31,
+            "gen_enum_discriminant: count == 0 or count > 2^31"
+        );
+        if !self.gen_bool(invalid_prob) {
+            return self.gen_range(0..count) as i32;
+        }
+        if self.gen() {
+            // `count` is not representable if `count == 2^31`
+            if count <= ::core::i32::MAX as u32 && self.gen() {
+                return count as i32;
+            }
+            return -1;
+        }
+        loop {
+            // At least half of all values are invalid
+            let x: i32 = self.gen();
+            if x < 0 || x as u32 >= count {
+                return x;
+            }
+        }
+    }
+
+    /// Generate a uniformly random [`BigUint`] in `[0, 2^bits)`.
+    ///
+    /// The required `ceil(bits / 8)` bytes are obtained with a single call to
+    /// [`fill_bytes`] and interpreted in little-endian order, with the bits
+    /// above `bits` masked off.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let x = thread_rng().gen_biguint(256);
+    /// assert!(x < BigUint::from(1u32) << 256);
+    /// ```
+    ///
+    /// [`BigUint`]: num_bigint::BigUint
+    /// [`fill_bytes`]: RngCore::fill_bytes
+    #[cfg(all(feature = "alloc", feature = "num-bigint"))]
+    #[cfg_attr(doc_cfg, doc(cfg(all(feature = "alloc", feature = "num-bigint"))))]
+    fn gen_biguint(&mut self, bits: u64) -> num_bigint::BigUint {
+        let len = ((bits + 7) / 8) as usize;
+        let mut bytes = alloc::vec![0u8; len];
+        self.fill_bytes(&mut bytes);
+        if bits % 8 != 0 {
+            bytes[len - 1] &= (1 << (bits % 8)) - 1;
+        }
+        num_bigint::BigUint::from_bytes_le(&bytes)
+    }
+
+    /// Generate a uniformly random [`BigUint`] in `[0, bound)`.
+    ///
+    /// Values with the bit length of `bound` are sampled with
+    /// [`Rng::gen_biguint`] until one is below `bound`. Since `bound` is at
+    /// least half of the sampled range, fewer than two samples are required
+    /// on average.
+    ///
+    /// # Panics
+    ///
+    /// If `bound` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let bound = BigUint::from(10u32).pow(30);
+    /// let x = thread_rng().gen_biguint_below(&bound);
+    /// assert!(x < bound);
+    /// ```
+    ///
+    /// [`BigUint`]: num_bigint::BigUint
+    #[cfg(all(feature = "alloc", feature = "num-bigint"))]
+    #[cfg_attr(doc_cfg, doc(cfg(all(feature = "alloc", feature = "num-bigint"))))]
+    fn gen_biguint_below(&mut self, bound: &num_bigint::BigUint) -> num_bigint::BigUint {
+        let bits = bound.bits();
+        assert!(bits > 0, "gen_biguint_below: bound is zero");
+        loop {
+            let x = self.gen_biguint(bits);
+            if x < *bound {
+                return x;
+            }
+        }
+    }
+
+    /// Generate a random `f64` in the open range `(low, high)`, excluding
+    /// both endpoints.
+    ///
+    /// A sample `u` from [`Open01`] is scaled into the range as
+    /// `low + (high - low) * u`. Since rounding may still yield an endpoint,
+    /// such results are rejected and resampled; this is rare unless the range
+    /// contains very few representable values.
+    ///
+    /// # Panics
+    ///
+    /// If `low >= high`, if either bound is not finite, or if there is no
+    /// `f64` strictly between `low` and `high`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let x = rng.gen_open_range(0.0, 1.0);
+    /// assert!(0.0 < x && x < 1.0);
+    /// println!("ln(x) = {}", x.ln());
+    /// ```
+    ///
+    /// [`Open01`]: distributions::Open01
+    fn gen_open_range(&mut self, low: f64, high: f64) -> f64 {
+        assert!(low < high, "gen_open_range: low >= high");
+        let scale = high - low;
+        assert!(
+            low.is_finite() && high.is_finite() && scale.is_finite(),
+            "gen_open_range: range is not finite"
+        );
+        let mid = low + scale * 0.5;
+        assert!(
+            low < mid && mid < high,
+            "gen_open_range: no value between low and high"
+        );
+        loop {
+            let u: f64 = self.sample(distributions::Open01);
+            let x = low + scale * u;
+            if low < x && x < high {
+                return x;
+            }
+        }
+    }
+
+    /// Generate random values using the [`Standard`] distribution until one
+    /// satisfies `pred`, making at most `max_tries` attempts.
+    ///
+    /// Returns the first accepted value, or `None` if all `max_tries`
+    /// samples were rejected. Unlike a plain rejection loop, this cannot loop
+    /// forever when `pred` is rarely or never satisfied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let odd: Option<u32> = rng.gen_until(|x| x % 2 == 1, 100);
+    /// println!("{:?}", odd);
+    /// ```
+    ///
+    /// [`Standard`]: distributions::Standard
+    fn gen_until<T, F>(&mut self, pred: F, max_tries: usize) -> Option<T>
+    where
+        Standard: Distribution<T>,
+        F: Fn(&T) -> bool,
+    {
+        for _ in 0..max_tries {
+            let x: T = self.gen();
+            if pred(&x) {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    /// Generate an arbitrary value of type `T` for use as test data.
+    ///
+    /// This is a shortcut for [`T::arbitrary(self)`](Arbitrary::arbitrary);
+    /// see [`Arbitrary`] for the supported types.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let data: Vec<u8> = rng.gen_arbitrary();
+    /// assert!(data.len() <= 32);
+    /// ```
+    fn gen_arbitrary<T: Arbitrary>(&mut self) -> T {
+        T::arbitrary(self)
+    }
+}
+
+impl<R: RngCore + ?Sized> Rng for R {}
+
+/// Types which may be filled with random data
+///
+/// This trait allows arrays to be efficiently filled with random data.
+///
+/// Implementations are expected to be portable across machines unless
+/// clearly documented otherwise (see the
+/// [Chapter on Portability](https://rust-random.github.io/book/portability.html)).
+pub trait Fill {
+    /// Fill self with random data
+    fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error>;
+}
+
+macro_rules! impl_fill_each {
+    () => {};
+    ($t:ty) => {
+        impl Fill for [$t] {
+            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+                for elt in self.iter_mut() {
+                    *elt = rng.gen();
+                }
+                Ok(())
+            }
+        }
+    };
+    ($t:ty, $($tt:ty,)*) => {
+        impl_fill_each!($t);
+        impl_fill_each!($($tt,)*);
+    };
+}
+
+impl_fill_each!(bool, char, f32, f64,);
+
+impl Fill for [u8] {
+    fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+        rng.try_fill_bytes(self)
+    }
+}
+
+macro_rules! impl_fill {
+    () => {};
+    ($t:ty) => {
+        impl Fill for [$t] {
+            #[inline(never)] // in micro benchmarks, this improves performance
+            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+                if self.len() > 0 {
+                    rng.try_fill_bytes(unsafe {
+                        slice::from_raw_parts_mut(self.as_mut_ptr()
+                            as *mut u8,
+                            self.len() * mem::size_of::<$t>()
+                        )
+                    })?;
+                    for x in self {
+                        *x = x.to_le();
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        impl Fill for [Wrapping<$t>] {
+            #[inline(never)]
+            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+                if self.len() > 0 {
+                    rng.try_fill_bytes(unsafe {
+                        slice::from_raw_parts_mut(self.as_mut_ptr()
+                            as *mut u8,
+                            self.len() * mem::size_of::<$t>()
+                        )
+                    })?;
+                    for x in self {
+                    *x = Wrapping(x.0.to_le());
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+    ($t:ty, $($tt:ty,)*) => {
+        impl_fill!($t);
+        // TODO: this could replace above impl once Rust #32463 is fixed
+        // impl_fill!(Wrapping<$t>);
+        impl_fill!($($tt,)*);
+    }
+}
+
+impl_fill!(u16, u32, u64, usize,);
+#[cfg(not(target_os = "emscripten"))]
+impl_fill!(u128);
+impl_fill!(i8, i16, i32, i64, isize,);
+#[cfg(not(target_os = "emscripten"))]
+impl_fill!(i128);
+
+#[cfg(feature = "min_const_gen")]
+impl<T, const N: usize> Fill for [T; N]
+where [T]: Fill
+{
+    fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+        self[..].try_fill(rng)
+    }
+}
+
+#[cfg(not(feature = "min_const_gen"))]
+macro_rules! impl_fill_arrays {
+    ($n:expr,) => {};
+    ($n:expr, $N:ident) => {
+        impl<T> Fill for [T; $n] where [T]: Fill {
+            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+                self[..].try_fill(rng)
+            }
+        }
+    };
+    ($n:expr, $N:ident, $($NN:ident,)*) => {
+        impl_fill_arrays!($n, $N);
+        impl_fill_arrays!($n - 1, $($NN,)*);
+    };
+    (!div $n:expr,) => {};
+    (!div $n:expr, $N:ident, $($NN:ident,)*) => {
+        impl_fill_arrays!($n, $N);
+        impl_fill_arrays!(!div $n / 2, $($NN,)*);
     };
-    ($n:expr, $N:ident, $($NN:ident,)*) => {
-        impl_fill_arrays!($n, $N);
-        impl_fill_arrays!($n - 1, $($NN,)*);
-    };
-    (!div $n:expr,) => {};
-    (!div $n:expr, $N:ident, $($NN:ident,)*) => {
-        impl_fill_arrays!($n, $N);
-        impl_fill_arrays!(!div $n / 2, $($NN,)*);
-    };
 }
 #[cfg(not(feature = "min_const_gen"))]
 #[rustfmt::skip]
@@ -431,173 +2087,1458 @@ impl_fill_arrays!(32, N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,
 #[cfg(not(feature = "min_const_gen"))]
 impl_fill_arrays!(!div 4096, N,N,N,N,N,N,N,);
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::test::rng;
-    use crate::rngs::mock::StepRng;
-    #[cfg(feature = "alloc")] use alloc::boxed::Box;
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::rng;
+    use crate::rngs::mock::StepRng;
+    #[cfg(feature = "alloc")] use alloc::boxed::Box;
+
+    #[test]
+    fn test_fill_bytes_default() {
+        let mut r = StepRng::new(0x11_22_33_44_55_66_77_88, 0);
+
+        // check every remainder mod 8, both in small and big vectors.
+        let lengths = [0, 1, 2, 3, 4, 5, 6, 7, 80, 81, 82, 83, 84, 85, 86, 87];
+        for &n in lengths.iter() {
+            let mut buffer = [0u8; 87];
+            let v = &mut buffer[0..n];
+            r.fill_bytes(v);
+
+            // use this to get nicer error messages.
+            for (i, &byte) in v.iter().enumerate() {
+                if byte == 0 {
+                    panic!("byte {} of {} is zero", i, n)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fill() {
+        let x = 9041086907909331047; // a random u64
+        let mut rng = StepRng::new(x, 0);
+
+        // Convert to byte sequence and back to u64; byte-swap twice if BE.
+        let mut array = [0u64; 2];
+        rng.fill(&mut array[..]);
+        assert_eq!(array, [x, x]);
+        assert_eq!(rng.next_u64(), x);
+
+        // Convert to bytes then u32 in LE order
+        let mut array = [0u32; 2];
+        rng.fill(&mut array[..]);
+        assert_eq!(array, [x as u32, (x >> 32) as u32]);
+        assert_eq!(rng.next_u32(), x as u32);
+
+        // Check equivalence using wrapped arrays
+        let mut warray = [Wrapping(0u32); 2];
+        rng.fill(&mut warray[..]);
+        assert_eq!(array[0], warray[0].0);
+        assert_eq!(array[1], warray[1].0);
+
+        // Check equivalence for generated floats
+        let mut array = [0f32; 2];
+        rng.fill(&mut array);
+        let gen: [f32; 2] = rng.gen();
+        assert_eq!(array, gen);
+    }
+
+    #[test]
+    fn test_fill_empty() {
+        let mut array = [0u32; 0];
+        let mut rng = StepRng::new(0, 1);
+        rng.fill(&mut array);
+        rng.fill(&mut array[..]);
+    }
+
+    #[test]
+    fn test_gen_range_int() {
+        let mut r = rng(101);
+        for _ in 0..1000 {
+            let a = r.gen_range(-4711..17);
+            assert!((-4711..17).contains(&a));
+            let a: i8 = r.gen_range(-3..42);
+            assert!((-3..42).contains(&a));
+            let a: u16 = r.gen_range(10..99);
+            assert!((10..99).contains(&a));
+            let a: i32 = r.gen_range(-100..2000);
+            assert!((-100..2000).contains(&a));
+            let a: u32 = r.gen_range(12..=24);
+            assert!((12..=24).contains(&a));
+
+            assert_eq!(r.gen_range(0u32..1), 0u32);
+            assert_eq!(r.gen_range(-12i64..-11), -12i64);
+            assert_eq!(r.gen_range(3_000_000..3_000_001), 3_000_000);
+        }
+    }
+
+    #[test]
+    fn test_gen_range_float() {
+        let mut r = rng(101);
+        for _ in 0..1000 {
+            let a = r.gen_range(-4.5..1.7);
+            assert!((-4.5..1.7).contains(&a));
+            let a = r.gen_range(-1.1..=-0.3);
+            assert!((-1.1..=-0.3).contains(&a));
+
+            assert_eq!(r.gen_range(0.0f32..=0.0), 0.);
+            assert_eq!(r.gen_range(-11.0..=-11.0), -11.);
+            assert_eq!(r.gen_range(3_000_000.0..=3_000_000.0), 3_000_000.);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_panic_int() {
+        #![allow(clippy::reversed_empty_ranges)]
+        let mut r = rng(102);
+        r.gen_range(5..-2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_panic_usize() {
+        #![allow(clippy::reversed_empty_ranges)]
+        let mut r = rng(103);
+        r.gen_range(5..2);
+    }
+
+    #[test]
+    fn test_gen_bool() {
+        #![allow(clippy::bool_assert_comparison)]
+
+        let mut r = rng(105);
+        for _ in 0..5 {
+            assert_eq!(r.gen_bool(0.0), false);
+            assert_eq!(r.gen_bool(1.0), true);
+        }
+    }
+
+    #[test]
+    fn test_rng_trait_object() {
+        use crate::distributions::{Distribution, Standard};
+        let mut rng = rng(109);
+        let mut r = &mut rng as &mut dyn RngCore;
+        r.next_u32();
+        r.gen::<i32>();
+        assert_eq!(r.gen_range(0..1), 0);
+        let _c: u8 = Standard.sample(&mut r);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_rng_boxed_trait() {
+        use crate::distributions::{Distribution, Standard};
+        let rng = rng(110);
+        let mut r = Box::new(rng) as Box<dyn RngCore>;
+        r.next_u32();
+        r.gen::<i32>();
+        assert_eq!(r.gen_range(0..1), 0);
+        let _c: u8 = Standard.sample(&mut r);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_gen_ratio_average() {
+        const NUM: u32 = 3;
+        const DENOM: u32 = 10;
+        const N: u32 = 100_000;
+
+        let mut sum: u32 = 0;
+        let mut rng = rng(111);
+        for _ in 0..N {
+            if rng.gen_ratio(NUM, DENOM) {
+                sum += 1;
+            }
+        }
+        // Have Binomial(N, NUM/DENOM) distribution
+        let expected = (NUM * N) / DENOM; // exact integer
+        assert!(((sum - expected) as i32).abs() < 500);
+    }
+
+    #[test]
+    fn test_gen_ratio_boundaries() {
+        let mut r = rng(181);
+        for &denom in [1, 7, ::core::u32::MAX].iter() {
+            assert!((0..100).all(|_| !r.gen_ratio(0, denom)));
+            assert!((0..100).all(|_| r.gen_ratio(denom, denom)));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_ratio_panic_zero_denominator() {
+        rng(182).gen_ratio(0, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_ratio_panic_numerator_too_large() {
+        rng(183).gen_ratio(4, 3);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_swap_remove_random() {
+        use alloc::vec::Vec;
+        let mut r = rng(112);
+        let mut values: Vec<u32> = (0..100).collect();
+        let mut seen = [false; 100];
+        let mut drained = Vec::new();
+        while let Some(x) = r.swap_remove_random(&mut values) {
+            assert!(!seen[x as usize]);
+            seen[x as usize] = true;
+            drained.push(x);
+        }
+        assert!(values.is_empty());
+        assert!(seen.iter().all(|&b| b));
+        // Draining should not simply reproduce the original order
+        assert!(drained.iter().zip(0..).any(|(&x, i)| x != i));
+        assert_eq!(r.swap_remove_random(&mut values), None);
+    }
+
+    #[test]
+    fn test_gen_geometric_p_half() {
+        const N: u32 = 64_000;
+        let mut r = rng(124);
+        let mut counts = [0u32; 65];
+        for _ in 0..N {
+            counts[r.gen_geometric_p_half() as usize] += 1;
+        }
+        // Each increment halves the frequency
+        let mut expected = N as f64 / 2.0;
+        for &c in counts[..6].iter() {
+            assert!((c as f64 - expected).abs() < 0.1 * expected);
+            expected /= 2.0;
+        }
+
+        let mut zero = crate::rngs::mock::StepRng::new(0, 0);
+        assert_eq!(zero.gen_geometric_p_half(), 64);
+        let mut odd = crate::rngs::mock::StepRng::new(1, 0);
+        assert_eq!(odd.gen_geometric_p_half(), 0);
+    }
+
+    #[test]
+    fn test_rotate_random() {
+        let mut r = rng(121);
+        let mut counts = [0u32; 5];
+        for _ in 0..5000 {
+            let mut values = [0, 1, 2, 3, 4];
+            r.rotate_random(&mut values);
+            // Same elements in the same cyclic order
+            let offset = values[0];
+            for (i, &v) in values.iter().enumerate() {
+                assert_eq!(v, (offset + i) % 5);
+            }
+            counts[offset] += 1;
+        }
+        for &c in counts.iter() {
+            assert!((c as i32 - 1000).abs() < 100);
+        }
+
+        let mut empty: [u8; 0] = [];
+        r.rotate_random(&mut empty);
+        let mut one = [7];
+        r.rotate_random(&mut one);
+        assert_eq!(one, [7]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_gen_range_shaped() {
+        // An exponent of 1 is exactly the uniform mapping of `Standard`
+        let mut r1 = rng(113);
+        let mut r2 = rng(113);
+        for _ in 0..100 {
+            let x = r1.gen_range_shaped(-2.0, 3.0, 1.0);
+            let u: f64 = r2.gen();
+            assert_eq!(x, -2.0 + 5.0 * u);
+            assert!((-2.0..3.0).contains(&x));
+        }
+
+        let mut r = rng(114);
+        let mean = |r: &mut dyn RngCore, exponent: f64| {
+            (0..10_000)
+                .map(|_| r.gen_range_shaped(0.0, 1.0, exponent))
+                .sum::<f64>() / 10_000.0
+        };
+        let m1 = mean(&mut r, 1.0);
+        let m2 = mean(&mut r, 2.0);
+        let m4 = mean(&mut r, 4.0);
+        // E[u^k] = 1 / (k + 1)
+        assert!((m1 - 0.5).abs() < 0.02);
+        assert!((m2 - 1.0 / 3.0).abs() < 0.02);
+        assert!((m4 - 0.2).abs() < 0.02);
+        assert!(m4 < m2 && m2 < m1);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_gen_range_shaped_panic() {
+        rng(115).gen_range_shaped(0.0, 1.0, 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_jitter() {
+        const N: usize = 10_000;
+        let mut r = rng(119);
+        let samples: Vec<f64> = (0..N).map(|_| r.jitter(5.0, 2.0)).collect();
+        let mean = samples.iter().sum::<f64>() / N as f64;
+        let var = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / N as f64;
+        assert!((mean - 5.0).abs() < 0.1);
+        assert!((var - 4.0).abs() < 0.3);
+
+        assert_eq!(r.jitter(3.0, 0.0), 3.0);
+
+        for _ in 0..N {
+            let x = r.jitter_clamped(0.9, 0.5, 0.0, 1.0);
+            assert!((0.0..=1.0).contains(&x));
+            // Noise far larger than the interval
+            let x = r.jitter_clamped(0.5, 100.0, -1.0, 1.0);
+            assert!((-1.0..=1.0).contains(&x));
+        }
+        assert_eq!(r.jitter_clamped(7.0, 1.0, 2.0, 2.0), 2.0);
+
+        // Reflection is symmetric: values centred in the interval keep their mean
+        let mean = (0..N).map(|_| r.jitter_clamped(0.5, 0.3, 0.0, 1.0)).sum::<f64>() / N as f64;
+        assert!((mean - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_jitter_panic() {
+        rng(120).jitter(0.0, -1.0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_gen_ip_in_cidr() {
+        use std::net::Ipv4Addr;
+        let mut r = rng(117);
+        let base = Ipv4Addr::new(10, 20, 30, 40);
+        for &prefix_len in &[32u8, 31, 24, 13, 8, 1] {
+            let mask = !(::core::u32::MAX as u64 >> prefix_len) as u32;
+            for _ in 0..100 {
+                let addr = r.gen_ip_in_cidr(base, prefix_len);
+                assert_eq!(u32::from(addr) & mask, u32::from(base) & mask);
+            }
+        }
+        assert_eq!(r.gen_ip_in_cidr(base, 32), base);
+
+        // For /0 every bit of the address can take either value
+        let (mut ones, mut zeros) = (0u32, 0u32);
+        for _ in 0..100 {
+            let addr = u32::from(r.gen_ip_in_cidr(base, 0));
+            ones |= addr;
+            zeros |= !addr;
+        }
+        assert_eq!(ones, ::core::u32::MAX);
+        assert_eq!(zeros, ::core::u32::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_gen_ip_in_cidr_panic() {
+        rng(118).gen_ip_in_cidr(std::net::Ipv4Addr::new(0, 0, 0, 0), 33);
+    }
+
+    #[test]
+    fn test_gen_open_range() {
+        let mut r = rng(122);
+        for &(low, high) in &[(0.0, 1.0), (-3.5, 2.0), (1e10, 1e10 + 0.01), (1.0, 1.0 + 8.0 * ::core::f64::EPSILON)] {
+            for _ in 0..1000 {
+                let x = r.gen_open_range(low, high);
+                assert!(low < x && x < high);
+            }
+        }
+        // Only a single value lies strictly between the bounds
+        let (low, high) = (1.0, 1.0 + 2.0 * ::core::f64::EPSILON);
+        for _ in 0..10 {
+            assert_eq!(r.gen_open_range(low, high), 1.0 + ::core::f64::EPSILON);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_open_range_panic() {
+        rng(123).gen_open_range(1.0, 1.0 + ::core::f64::EPSILON);
+    }
+
+    #[test]
+    fn test_gen_until() {
+        let mut r = rng(116);
+        let tries = core::cell::Cell::new(0);
+        let x: Option<u32> = r.gen_until(
+            |_| {
+                tries.set(tries.get() + 1);
+                false
+            },
+            10,
+        );
+        assert_eq!(x, None);
+        assert_eq!(tries.get(), 10);
+
+        let x: Option<u8> = r.gen_until(|&x| x < 128, 100);
+        assert!(x.unwrap() < 128);
+        let x: Option<u8> = r.gen_until(|_| true, 0);
+        assert_eq!(x, None);
+    }
+
+    #[test]
+    fn test_gen_sign() {
+        let mut r = rng(125);
+        const N: usize = 10_000;
+        let mut sum = 0i32;
+        for _ in 0..N {
+            let x: i32 = r.gen_sign();
+            assert!(x == 1 || x == -1);
+            sum += x;
+        }
+        // Standard deviation of the sum is sqrt(N) = 100
+        assert!(sum.abs() < 400);
+        let x: f64 = r.gen_sign();
+        assert!(x == 1.0 || x == -1.0);
+    }
+
+    #[test]
+    fn test_gen_signed_range() {
+        let mut r = rng(126);
+        let mut negative = 0;
+        for &magnitude in &[1.0, 0.001, 1e300] {
+            for _ in 0..1000 {
+                let x = r.gen_signed_range(magnitude);
+                assert!(-magnitude < x && x < magnitude);
+                if x < 0.0 {
+                    negative += 1;
+                }
+            }
+        }
+        assert!(negative > 1300 && negative < 1700);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_signed_range_panic() {
+        rng(127).gen_signed_range(0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_gen_near() {
+        let mut r = rng(128);
+        let (target, spread) = (5.0, 2.0);
+        let mut bins = [0usize; 8];
+        for _ in 0..20_000 {
+            let x = r.gen_near(target, spread);
+            assert!(target - spread <= x && x <= target + spread);
+            bins[(((x - 3.0) * 2.0) as usize).min(7)] += 1;
+        }
+        // The density rises linearly towards the mode at `target`
+        for i in 0..3 {
+            assert!(bins[i] < bins[i + 1]);
+            assert!(bins[7 - i] < bins[6 - i]);
+        }
+        assert_eq!(r.gen_near(target, 0.0), target);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_gen_near_panic() {
+        rng(129).gen_near(0.0, -1.0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_gen_matrix() {
+        use crate::distributions::Uniform;
+        let distr = Uniform::new(0.0, 1.0);
+
+        let m = rng(130).gen_matrix(3, 4, &distr);
+        assert_eq!(m.len(), 3);
+        for row in &m {
+            assert_eq!(row.len(), 4);
+            assert!(row.iter().all(|&x| (0.0..1.0).contains(&x)));
+        }
+        let flat = rng(130).gen_matrix_flat(3, 4, &distr);
+        assert_eq!(flat.len(), 12);
+        for (i, row) in m.iter().enumerate() {
+            assert_eq!(&flat[i * 4..(i + 1) * 4], &row[..]);
+        }
+
+        assert!(rng(131).gen_matrix(0, 4, &distr).is_empty());
+        assert!(rng(131).gen_matrix(2, 0, &distr).iter().all(|row| row.is_empty()));
+        assert!(rng(131).gen_matrix_flat(2, 0, &distr).is_empty());
+    }
+
+    #[test]
+    fn test_choose_from_iter() {
+        let mut r = rng(132);
+        assert_eq!(r.choose_from_iter(core::iter::empty::<u32>()), None);
+        assert_eq!(r.choose_from_iter(core::iter::once(7)), Some(7));
+
+        // `Filter` is not an `ExactSizeIterator`
+        const N: usize = 10_000;
+        let mut counts = [0usize; 5];
+        for _ in 0..N {
+            let x = r.choose_from_iter((0..10).filter(|x| x % 2 == 0)).unwrap();
+            counts[x / 2] += 1;
+        }
+        for &c in counts.iter() {
+            // Expected 2000 each, with standard deviation 40
+            assert!((c as i32 - 2000).abs() < 200);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_gen_permutation_index() {
+        fn rank(permutation: &[u32]) -> u64 {
+            // Inverse of the Lehmer code decoding
+            let mut rank = 0;
+            for (i, &x) in permutation.iter().enumerate() {
+                let smaller_later = permutation[i + 1..].iter().filter(|&&y| y < x).count();
+                rank = rank * (permutation.len() - i) as u64 + smaller_later as u64;
+            }
+            rank
+        }
+
+        assert!(rng(133).gen_permutation_index(0).is_empty());
+        for &n in &[1usize, 2, 5, 12, 20] {
+            let factorial = (1..=n as u64).product::<u64>();
+            for seed in 134..144 {
+                let permutation = rng(seed).gen_permutation_index(n);
+                let mut sorted = permutation.clone();
+                sorted.sort_unstable();
+                assert!(sorted.iter().enumerate().all(|(i, &x)| x == i as u32));
+
+                // The index is exactly the value sampled from `[0, n!)`
+                let index = rng(seed).gen_range(0..factorial);
+                assert_eq!(rank(&permutation), index);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "alloc")]
+    fn test_gen_permutation_index_panic() {
+        rng(144).gen_permutation_index(21);
+    }
+
+    #[test]
+    fn test_gen_hsv() {
+        let mut r = rng(145);
+        let mut hue_bins = [0usize; 6];
+        for _ in 0..6000 {
+            let [h, s, v] = r.gen_hsv((0.25, 0.5), (0.9, 0.9));
+            assert!((0.0..360.0).contains(&h));
+            assert!((0.25..=0.5).contains(&s));
+            assert_eq!(v, 0.9);
+            hue_bins[(h / 60.0) as usize] += 1;
+        }
+        // The hue covers the full circle
+        assert!(hue_bins.iter().all(|&c| c > 800));
+
+        let [h, s, v] = r.gen_hsv((0.0, 1.0), (0.0, 1.0));
+        assert!(h < 360.0 && s <= 1.0 && v <= 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_hsv_panic() {
+        rng(146).gen_hsv((0.5, 0.4), (0.0, 1.0));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_gen_correlated_normal() {
+        const N: usize = 20_000;
+        let mut r = rng(147);
+        for &rho in &[-1.0, -0.5, 0.0, 0.3, 0.9, 1.0] {
+            let (mut sx, mut sy, mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+            for _ in 0..N {
+                let (x, y) = r.gen_correlated_normal(rho);
+                sx += x;
+                sy += y;
+                sxx += x * x;
+                syy += y * y;
+                sxy += x * y;
+            }
+            let n = N as f64;
+            let cov = sxy / n - sx / n * sy / n;
+            let var_x = sxx / n - (sx / n) * (sx / n);
+            let var_y = syy / n - (sy / n) * (sy / n);
+            let corr = cov / (var_x * var_y).sqrt();
+            assert!((corr - rho).abs() < 0.02, "rho = {}, corr = {}", rho, corr);
+            assert!((var_y - 1.0).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_gen_correlated_normal_panic() {
+        rng(148).gen_correlated_normal(1.5);
+    }
+
+    #[test]
+    fn test_gen_quantized_f64() {
+        const N: usize = 10_000;
+        let mut r = rng(149);
+        let grid = [-1.0, -0.5, 0.0, 0.5, 1.0];
+        let mut counts = [0usize; 5];
+        for _ in 0..N {
+            let x = r.gen_quantized_f64(-1.0, 1.0, 5);
+            let i = grid.iter().position(|&g| g == x).unwrap();
+            counts[i] += 1;
+        }
+        for &count in &counts {
+            assert!((count as f64 / N as f64 - 0.2).abs() < 0.02);
+        }
+
+        for _ in 0..100 {
+            let x = r.gen_quantized_f64(0.1, 0.7, 7);
+            assert!(
+                (0..7).any(|i| {
+                    let t = i as f64 / 6.0;
+                    x == 0.1 * (1.0 - t) + 0.7 * t
+                })
+            );
+            assert!((0.1..=0.7).contains(&x));
+        }
+        assert_eq!(r.gen_quantized_f64(3.0, 4.0, 1), 3.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_quantized_f64_no_levels() {
+        rng(150).gen_quantized_f64(0.0, 1.0, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_quantized_f64_empty_range() {
+        rng(151).gen_quantized_f64(1.0, 1.0, 3);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_gen_combination() {
+        let mut r = rng(152);
+        for &(n, k) in &[(0, 0), (1, 1), (10, 0), (10, 3), (100, 99), (1000, 10)] {
+            let c = r.gen_combination(n, k);
+            assert_eq!(c.len(), k);
+            assert!(c.windows(2).all(|w| w[0] < w[1]));
+            assert!(c.iter().all(|&i| i < n));
+        }
+        assert_eq!(r.gen_combination(4, 7), [0, 1, 2, 3]);
+
+        // All 10 subsets of size 2 of `0..5` are equally likely
+        const N: usize = 10_000;
+        let mut counts = [0usize; 32];
+        for _ in 0..N {
+            let c = r.gen_combination(5, 2);
+            counts[(1 << c[0]) | (1 << c[1])] += 1;
+        }
+        let subsets: Vec<usize> = (0..32usize)
+            .filter(|m| m.count_ones() == 2)
+            .collect();
+        assert_eq!(subsets.len(), 10);
+        for m in subsets {
+            assert!((counts[m] as f64 / N as f64 - 0.1).abs() < 0.015);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_gen_luhn_number() {
+        // Reference implementation, checking from the right
+        fn luhn_valid(s: &str) -> bool {
+            let mut sum = 0;
+            for (i, c) in s.chars().rev().enumerate() {
+                let mut d = c.to_digit(10).unwrap();
+                if i % 2 == 1 {
+                    d *= 2;
+                    if d > 9 {
+                        d -= 9;
+                    }
+                }
+                sum += d;
+            }
+            sum % 10 == 0
+        }
+        assert!(luhn_valid("79927398713"));
+        assert!(!luhn_valid("79927398710"));
+
+        let mut r = rng(153);
+        for digits in 1..20 {
+            for _ in 0..50 {
+                let n = r.gen_luhn_number(digits);
+                assert_eq!(n.len(), digits);
+                assert!(n.chars().all(|c| c.is_ascii_digit()));
+                assert!(luhn_valid(&n), "{}", n);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "alloc")]
+    fn test_gen_luhn_number_panic() {
+        rng(154).gen_luhn_number(0);
+    }
+
+    #[test]
+    fn test_fill_uniform_f64() {
+        // `StepRng` implements `fill_bytes` via `next_u64`, so the results
+        // match sampling from `Standard`
+        let mut r = StepRng::new(0x0123_4567_89ab_cdef, 0x9e37_79b9_7f4a_7c15);
+        let mut expected_rng = r.clone();
+        let mut buf = [0.0; 33];
+        r.fill_uniform_f64(&mut buf);
+        for &x in buf.iter() {
+            assert_eq!(x, expected_rng.gen::<f64>());
+        }
+
+        let mut r = rng(155);
+        let mut buf = [0.0; 1000];
+        r.fill_uniform_f64(&mut buf);
+        assert!(buf.iter().all(|&x| (0.0..1.0).contains(&x)));
+        let mean = buf.iter().sum::<f64>() / buf.len() as f64;
+        assert!((mean - 0.5).abs() < 0.05);
+
+        r.fill_uniform_f64(&mut []);
+        let mut max = [0.0];
+        StepRng::new(::core::u64::MAX, 0).fill_uniform_f64(&mut max);
+        assert_eq!(max[0], 1.0 - ::core::f64::EPSILON / 2.0);
+    }
+
+    #[test]
+    fn test_gen_bits_in_mask() {
+        const N: usize = 10_000;
+        let mut r = rng(156);
+        let mask = 0x8000_0000_00f0_0001u64;
+        let mut counts = [0usize; 64];
+        let mut counts_weighted = [0usize; 64];
+        for _ in 0..N {
+            let x = r.gen_bits_in_mask(mask);
+            let y = r.gen_bits_in_mask_weighted(mask, 0.2);
+            assert_eq!(x & !mask, 0);
+            assert_eq!(y & !mask, 0);
+            for i in 0..64 {
+                counts[i] += (x >> i & 1) as usize;
+                counts_weighted[i] += (y >> i & 1) as usize;
+            }
+        }
+        for i in 0..64 {
+            if mask >> i & 1 == 1 {
+                assert!((counts[i] as f64 / N as f64 - 0.5).abs() < 0.02);
+                assert!((counts_weighted[i] as f64 / N as f64 - 0.2).abs() < 0.02);
+            } else {
+                assert_eq!(counts[i] + counts_weighted[i], 0);
+            }
+        }
+
+        assert_eq!(r.gen_bits_in_mask(0), 0);
+        assert_eq!(r.gen_bits_in_mask_weighted(mask, 0.0), 0);
+        assert_eq!(r.gen_bits_in_mask_weighted(mask, 1.0), mask);
+        assert_eq!(r.gen_bits_in_mask_weighted(!0, 1.0), !0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_bits_in_mask_weighted_panic() {
+        rng(157).gen_bits_in_mask_weighted(1, 1.5);
+    }
+
+    #[test]
+    fn test_gen_enum_discriminant() {
+        const N: usize = 10_000;
+        let mut r = rng(158);
+        let mut counts = [0usize; 5];
+        let mut invalid = 0;
+        let mut adjacent = 0;
+        for _ in 0..N {
+            let d = r.gen_enum_discriminant(5, 0.1);
+            if (0..5).contains(&d) {
+                counts[d as usize] += 1;
+            } else {
+                invalid += 1;
+                adjacent += (d == -1 || d == 5) as usize;
+            }
+        }
+        for &c in &counts {
+            assert!((c as f64 / N as f64 - 0.18).abs() < 0.02);
+        }
+        assert!((invalid as f64 / N as f64 - 0.1).abs() < 0.01);
+        assert!((adjacent as f64 / invalid as f64 - 0.5).abs() < 0.06);
+
+        for _ in 0..100 {
+            assert!((0..3).contains(&r.gen_enum_discriminant(3, 0.0)));
+            let d = r.gen_enum_discriminant(3, 1.0);
+            assert!(!(0..3).contains(&d));
+            assert!(r.gen_enum_discriminant(1 << 31, 1.0) < 0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_enum_discriminant_panic() {
+        rng(159).gen_enum_discriminant(0, 0.5);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "num-bigint"))]
+    fn test_gen_biguint() {
+        use num_bigint::BigUint;
+
+        let mut r = rng(160);
+        let limit = BigUint::from(1u32) << 256;
+        let mut max_bits = 0;
+        for _ in 0..100 {
+            let x = r.gen_biguint(256);
+            assert!(x < limit);
+            max_bits = max_bits.max(x.bits());
+        }
+        assert_eq!(max_bits, 256);
+        for &bits in &[0, 1, 7, 9, 63, 65] {
+            assert!(r.gen_biguint(bits).bits() <= bits);
+        }
+
+        for &bound in &[1u32, 2, 3, 255, 256, 257, 1000] {
+            let bound = BigUint::from(bound);
+            let mut seen_max = false;
+            for _ in 0..2000 {
+                let x = r.gen_biguint_below(&bound);
+                assert!(x < bound);
+                seen_max |= x == &bound - 1u32;
+            }
+            assert!(seen_max);
+        }
+        let bound = (BigUint::from(1u32) << 200) + 1u32;
+        for _ in 0..100 {
+            assert!(r.gen_biguint_below(&bound) < bound);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(all(feature = "alloc", feature = "num-bigint"))]
+    fn test_gen_biguint_below_zero() {
+        rng(161).gen_biguint_below(&num_bigint::BigUint::from(0u32));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_perturb_simplex() {
+        let mut r = rng(162);
+        let original = [0.5, 0.3, 0.0, 0.2];
+        let distance = |r: &mut dyn RngCore, concentration| {
+            let mut total = 0.0;
+            for _ in 0..1000 {
+                let mut probs = original;
+                r.perturb_simplex(&mut probs, concentration);
+                assert!((probs.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+                assert!(probs.iter().all(|&p| p >= 0.0));
+                assert_eq!(probs[2], 0.0);
+                total += probs.iter().zip(original.iter()).map(|(a, b)| (a - b).abs()).sum::<f64>();
+            }
+            total / 1000.0
+        };
+        let d_low = distance(&mut r, 1.0);
+        let d_mid = distance(&mut r, 10.0);
+        let d_high = distance(&mut r, 1000.0);
+        assert!(d_low > d_mid && d_mid > d_high);
+        assert!(d_high < 0.05);
+
+        // The mean is preserved
+        let mut mean = [0.0; 4];
+        for _ in 0..10_000 {
+            let mut probs = original;
+            r.perturb_simplex(&mut probs, 5.0);
+            for (m, p) in mean.iter_mut().zip(probs.iter()) {
+                *m += p / 10_000.0;
+            }
+        }
+        for (m, p) in mean.iter().zip(original.iter()) {
+            assert!((m - p).abs() < 0.01);
+        }
+
+        // Tiny parameters do not underflow
+        let mut probs = [0.5, 0.5];
+        r.perturb_simplex(&mut probs, 1e-3);
+        assert!((probs[0] + probs[1] - 1.0).abs() < 1e-12);
+    }
 
     #[test]
-    fn test_fill_bytes_default() {
-        let mut r = StepRng::new(0x11_22_33_44_55_66_77_88, 0);
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_perturb_simplex_panic() {
+        rng(163).perturb_simplex(&mut [0.0, 0.0], 1.0);
+    }
 
-        // check every remainder mod 8, both in small and big vectors.
-        let lengths = [0, 1, 2, 3, 4, 5, 6, 7, 80, 81, 82, 83, 84, 85, 86, 87];
-        for &n in lengths.iter() {
-            let mut buffer = [0u8; 87];
-            let v = &mut buffer[0..n];
-            r.fill_bytes(v);
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_choose_recency_biased() {
+        let mut r = rng(164);
+        let empty: [u8; 0] = [];
+        assert_eq!(r.choose_recency_biased(&empty, 0.5), None);
+        assert_eq!(r.choose_recency_biased(&[7], 0.5), Some(&7));
 
-            // use this to get nicer error messages.
-            for (i, &byte) in v.iter().enumerate() {
-                if byte == 0 {
-                    panic!("byte {} of {} is zero", i, n)
-                }
+        const N: usize = 100_000;
+        let values = [0, 1, 2, 3, 4];
+        let mut counts = [0usize; 5];
+        for _ in 0..N {
+            counts[*r.choose_recency_biased(&values, 0.5).unwrap()] += 1;
+        }
+        // The most recent element is most likely, the first least likely
+        assert_eq!(counts.iter().max(), Some(&counts[4]));
+        assert_eq!(counts.iter().min(), Some(&counts[0]));
+        // Expected probabilities are 2^i / 31
+        for (i, &c) in counts.iter().enumerate() {
+            let expected = (1 << i) as f64 / 31.0;
+            assert!((c as f64 / N as f64 - expected).abs() < 0.01);
+        }
+        for i in 0..4 {
+            let ratio = counts[i] as f64 / counts[i + 1] as f64;
+            assert!((ratio - 0.5).abs() < 0.05);
+        }
+
+        // Without decay all elements are equally likely
+        let mut counts = [0usize; 5];
+        for _ in 0..N {
+            counts[*r.choose_recency_biased(&values, 1.0).unwrap()] += 1;
+        }
+        for &c in counts.iter() {
+            assert!((c as f64 / N as f64 - 0.2).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_choose_recency_biased_panic() {
+        rng(165).choose_recency_biased(&[1, 2, 3], 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_gen_brownian_path() {
+        let mut r = rng(166);
+        assert_eq!(r.gen_brownian_path(0, 1.0, 1.0), [0.0]);
+        assert_eq!(r.gen_brownian_path(3, 1.0, 0.0), [0.0; 4]);
+
+        const N: usize = 10_000;
+        let (steps, dt, volatility) = (20, 0.5, 2.0);
+        let mut sum = [0.0; 21];
+        let mut sum_sq = [0.0; 21];
+        for _ in 0..N {
+            let path = r.gen_brownian_path(steps, dt, volatility);
+            assert_eq!(path.len(), steps + 1);
+            assert_eq!(path[0], 0.0);
+            for (k, &x) in path.iter().enumerate() {
+                sum[k] += x;
+                sum_sq[k] += x * x;
             }
         }
+        for &k in [1, 5, 20].iter() {
+            let mean = sum[k] / N as f64;
+            let var = sum_sq[k] / N as f64 - mean * mean;
+            let expected = k as f64 * volatility * volatility * dt;
+            assert!(mean.abs() < 0.05 * expected.sqrt());
+            assert!((var / expected - 1.0).abs() < 0.05, "k = {}: {}", k, var);
+        }
     }
 
     #[test]
-    fn test_fill() {
-        let x = 9041086907909331047; // a random u64
-        let mut rng = StepRng::new(x, 0);
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_gen_brownian_path_panic() {
+        rng(167).gen_brownian_path(10, 1.0, -1.0);
+    }
 
-        // Convert to byte sequence and back to u64; byte-swap twice if BE.
-        let mut array = [0u64; 2];
-        rng.fill(&mut array[..]);
-        assert_eq!(array, [x, x]);
-        assert_eq!(rng.next_u64(), x);
+    #[test]
+    fn test_gen_mod() {
+        let mut r = rng(168);
+        assert_eq!(r.gen_mod(1), 0);
 
-        // Convert to bytes then u32 in LE order
-        let mut array = [0u32; 2];
-        rng.fill(&mut array[..]);
-        assert_eq!(array, [x as u32, (x >> 32) as u32]);
-        assert_eq!(rng.next_u32(), x as u32);
+        // A prime of about 3/4 * 2^64, where a naive `gen::<u64>() % p`
+        // yields values below 2^64 - p twice as often as others
+        const P: u64 = 13835058055282163729;
+        const BINS: usize = 8;
+        const N: usize = 80_000;
+        let chi_squared = |counts: &[usize; BINS]| {
+            let expected = (N / BINS) as f64;
+            counts.iter().map(|&c| {
+                let d = c as f64 - expected;
+                d * d / expected
+            }).sum::<f64>()
+        };
+        let bin = |x: u64| (x / (P / BINS as u64 + 1)) as usize;
 
-        // Check equivalence using wrapped arrays
-        let mut warray = [Wrapping(0u32); 2];
-        rng.fill(&mut warray[..]);
-        assert_eq!(array[0], warray[0].0);
-        assert_eq!(array[1], warray[1].0);
+        let mut counts = [0; BINS];
+        let mut naive_counts = [0; BINS];
+        for _ in 0..N {
+            let x = r.gen_mod(P);
+            assert!(x < P);
+            counts[bin(x)] += 1;
+            naive_counts[bin(r.gen::<u64>() % P)] += 1;
+        }
+        // Critical value for 7 degrees of freedom at p = 0.001 is 24.3
+        assert!(chi_squared(&counts) < 24.3);
+        assert!(chi_squared(&naive_counts) > 1000.0);
 
-        // Check equivalence for generated floats
-        let mut array = [0f32; 2];
-        rng.fill(&mut array);
-        let gen: [f32; 2] = rng.gen();
-        assert_eq!(array, gen);
+        // A small prime
+        let mut counts = [0; 7];
+        for _ in 0..70_000 {
+            counts[r.gen_mod(7) as usize] += 1;
+        }
+        let chi_squared = counts.iter().map(|&c| {
+            let d = c as f64 - 10_000.0;
+            d * d / 10_000.0
+        }).sum::<f64>();
+        // Critical value for 6 degrees of freedom at p = 0.001 is 22.5
+        assert!(chi_squared < 22.5);
     }
 
     #[test]
-    fn test_fill_empty() {
-        let mut array = [0u32; 0];
-        let mut rng = StepRng::new(0, 1);
-        rng.fill(&mut array);
-        rng.fill(&mut array[..]);
+    #[should_panic]
+    fn test_gen_mod_panic() {
+        rng(169).gen_mod(0);
     }
 
     #[test]
-    fn test_gen_range_int() {
-        let mut r = rng(101);
-        for _ in 0..1000 {
-            let a = r.gen_range(-4711..17);
-            assert!((-4711..17).contains(&a));
-            let a: i8 = r.gen_range(-3..42);
-            assert!((-3..42).contains(&a));
-            let a: u16 = r.gen_range(10..99);
-            assert!((10..99).contains(&a));
-            let a: i32 = r.gen_range(-100..2000);
-            assert!((-100..2000).contains(&a));
-            let a: u32 = r.gen_range(12..=24);
-            assert!((12..=24).contains(&a));
+    #[cfg(feature = "alloc")]
+    fn test_gen_semver() {
+        // Validate against the grammar of https://semver.org
+        fn is_numeric(id: &str) -> bool {
+            !id.is_empty()
+                && id.bytes().all(|b| b.is_ascii_digit())
+                && (id == "0" || !id.starts_with('0'))
+        }
+        fn is_identifier(id: &str) -> bool {
+            !id.is_empty() && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        }
+        fn is_valid(version: &str) -> (bool, bool) {
+            let (rest, build) = match version.find('+') {
+                Some(i) => (&version[..i], Some(&version[i + 1..])),
+                None => (version, None),
+            };
+            let (core, pre) = match rest.find('-') {
+                Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+                None => (rest, None),
+            };
+            let core_ok = core.split('.').count() == 3 && core.split('.').all(is_numeric);
+            let pre_ok = pre.unwrap_or("a").split('.').all(|id| {
+                is_identifier(id) && (!id.bytes().all(|b| b.is_ascii_digit()) || is_numeric(id))
+            });
+            let build_ok = build.unwrap_or("a").split('.').all(is_identifier);
+            (core_ok && pre_ok && build_ok, pre.is_some())
+        }
 
-            assert_eq!(r.gen_range(0u32..1), 0u32);
-            assert_eq!(r.gen_range(-12i64..-11), -12i64);
-            assert_eq!(r.gen_range(3_000_000..3_000_001), 3_000_000);
+        assert!(is_valid("1.0.0-alpha.1+001").0);
+        assert!(!is_valid("1.02.0").0);
+        assert!(!is_valid("1.0.0-01").0);
+        assert!(!is_valid("1.0").0);
+
+        let mut r = rng(170);
+        const N: usize = 10_000;
+        let mut pre_releases = 0;
+        let mut builds = 0;
+        for _ in 0..N {
+            let version = r.gen_semver();
+            let (valid, pre) = is_valid(&version);
+            assert!(valid, "invalid version {}", version);
+            pre_releases += pre as usize;
+            builds += version.contains('+') as usize;
         }
+        assert!((pre_releases as f64 / N as f64 - 0.25).abs() < 0.02);
+        assert!((builds as f64 / N as f64 - 0.125).abs() < 0.02);
     }
 
     #[test]
-    fn test_gen_range_float() {
-        let mut r = rng(101);
-        for _ in 0..1000 {
-            let a = r.gen_range(-4.5..1.7);
-            assert!((-4.5..1.7).contains(&a));
-            let a = r.gen_range(-1.1..=-0.3);
-            assert!((-1.1..=-0.3).contains(&a));
+    fn test_gen_lattice_in_disc() {
+        let mut r = rng(171);
+        assert_eq!(r.gen_lattice_in_disc(0), (0, 0));
 
-            assert_eq!(r.gen_range(0.0f32..=0.0), 0.);
-            assert_eq!(r.gen_range(-11.0..=-11.0), -11.);
-            assert_eq!(r.gen_range(3_000_000.0..=3_000_000.0), 3_000_000.);
+        const N: usize = 40_000;
+        let mut quadrants = [0usize; 4];
+        for _ in 0..N {
+            let (x, y) = r.gen_lattice_in_disc(5);
+            assert!(x * x + y * y <= 25);
+            // Points on the axes are not counted
+            match (x.signum(), y.signum()) {
+                (1, 1) => quadrants[0] += 1,
+                (-1, 1) => quadrants[1] += 1,
+                (-1, -1) => quadrants[2] += 1,
+                (1, -1) => quadrants[3] += 1,
+                _ => (),
+            }
         }
+        // 81 lattice points lie in the disc, 15 of them in each quadrant
+        for &q in quadrants.iter() {
+            assert!((q as f64 / N as f64 - 15.0 / 81.0).abs() < 0.01);
+        }
+
+        let (x, y) = r.gen_lattice_in_disc(::core::i32::MAX);
+        let max = i64::from(::core::i32::MAX);
+        assert!(i64::from(x).pow(2) + i64::from(y).pow(2) <= max * max);
     }
 
     #[test]
     #[should_panic]
-    fn test_gen_range_panic_int() {
-        #![allow(clippy::reversed_empty_ranges)]
-        let mut r = rng(102);
-        r.gen_range(5..-2);
+    fn test_gen_lattice_in_disc_panic() {
+        rng(172).gen_lattice_in_disc(-1);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_shuffle_with_pinned() {
+        let mut r = rng(173);
+        let pinned = [true, false, false, true, false, true];
+        let mut counts = [[0usize; 6]; 6];
+        for _ in 0..6000 {
+            let mut values = [0, 1, 2, 3, 4, 5];
+            r.shuffle_with_pinned(&mut values, &pinned);
+            assert_eq!((values[0], values[3], values[5]), (0, 3, 5));
+            let mut sorted = values;
+            sorted.sort_unstable();
+            assert_eq!(sorted, [0, 1, 2, 3, 4, 5]);
+            for (pos, &v) in values.iter().enumerate() {
+                counts[v][pos] += 1;
+            }
+        }
+        // Each unpinned element is equally likely at each unpinned position
+        for &v in [1, 2, 4].iter() {
+            for &pos in [1, 2, 4].iter() {
+                assert!((counts[v][pos] as f64 - 2000.0).abs() < 150.0);
+            }
+        }
+
+        let mut empty: [u8; 0] = [];
+        r.shuffle_with_pinned(&mut empty, &[]);
     }
 
     #[test]
     #[should_panic]
-    fn test_gen_range_panic_usize() {
-        #![allow(clippy::reversed_empty_ranges)]
-        let mut r = rng(103);
-        r.gen_range(5..2);
+    #[cfg(feature = "alloc")]
+    fn test_shuffle_with_pinned_panic() {
+        rng(174).shuffle_with_pinned(&mut [1, 2, 3], &[false, false]);
     }
 
     #[test]
-    fn test_gen_bool() {
-        #![allow(clippy::bool_assert_comparison)]
+    fn test_fill_bytes_weight() {
+        let mut r = rng(175);
+        let popcount = |buf: &[u8]| buf.iter().map(|b| b.count_ones()).sum::<u32>() as usize;
 
-        let mut r = rng(105);
-        for _ in 0..5 {
-            assert_eq!(r.gen_bool(0.0), false);
-            assert_eq!(r.gen_bool(1.0), true);
+        let mut buf = [0xAAu8; 4];
+        r.fill_bytes_weight(&mut buf, 0);
+        assert_eq!(buf, [0; 4]);
+        r.fill_bytes_weight(&mut buf, 32);
+        assert_eq!(buf, [0xFF; 4]);
+        r.fill_bytes_weight(&mut [], 0);
+
+        // Each bit position is set equally often
+        const N: usize = 20_000;
+        const ONES: usize = 5;
+        let mut counts = [0usize; 32];
+        for _ in 0..N {
+            r.fill_bytes_weight(&mut buf, ONES);
+            assert_eq!(popcount(&buf), ONES);
+            for (pos, count) in counts.iter_mut().enumerate() {
+                *count += ((buf[pos / 8] >> (pos % 8)) & 1) as usize;
+            }
         }
+        let expected = (N * ONES) as f64 / 32.0;
+        let chi_squared = counts.iter().map(|&c| {
+            let d = c as f64 - expected;
+            d * d / expected
+        }).sum::<f64>();
+        // Critical value for 31 degrees of freedom at p = 0.001 is 61.1
+        assert!(chi_squared < 61.1);
     }
 
     #[test]
-    fn test_rng_trait_object() {
-        use crate::distributions::{Distribution, Standard};
-        let mut rng = rng(109);
-        let mut r = &mut rng as &mut dyn RngCore;
-        r.next_u32();
-        r.gen::<i32>();
-        assert_eq!(r.gen_range(0..1), 0);
-        let _c: u8 = Standard.sample(&mut r);
+    #[should_panic]
+    fn test_fill_bytes_weight_panic() {
+        rng(176).fill_bytes_weight(&mut [0u8; 2], 17);
+    }
+
+    #[test]
+    fn test_choose_excluding() {
+        let mut r = rng(177);
+        let values = [0, 1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(r.choose_excluding(&values, &values), None);
+        assert_eq!(r.choose_excluding(&[] as &[u8], &[]), None);
+        assert_eq!(r.choose_excluding(&values, &[0, 1, 2, 3, 4, 5, 7]), Some(&6));
+
+        const N: usize = 40_000;
+        let excluded = [1, 3, 4, 9];
+        let mut counts = [0usize; 8];
+        for _ in 0..N {
+            let x = *r.choose_excluding(&values, &excluded).unwrap();
+            assert!(!excluded.contains(&x));
+            counts[x] += 1;
+        }
+        for &i in [0, 2, 5, 6, 7].iter() {
+            assert!((counts[i] as f64 / N as f64 - 0.2).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_gen_gaussian_blobs() {
+        let mut r = rng(178);
+        assert!(r.gen_gaussian_blobs(&[], 1.0, 10).is_empty());
+
+        let centers = [[0.0, 0.0], [20.0, 0.0], [0.0, 20.0], [-15.0, -15.0]];
+        let points = r.gen_gaussian_blobs(&centers, 1.5, 500);
+        assert_eq!(points.len(), centers.len() * 500);
+        let dist2 = |a: [f64; 2], b: [f64; 2]| (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2);
+        let mut sum_sq = 0.0;
+        for &(p, label) in points.iter() {
+            let nearest = (0..centers.len())
+                .min_by(|&i, &j| dist2(p, centers[i]).partial_cmp(&dist2(p, centers[j])).unwrap())
+                .unwrap();
+            assert_eq!(nearest, label);
+            sum_sq += dist2(p, centers[label]);
+        }
+        // Each coordinate has variance std_dev^2
+        let var = sum_sq / (2 * points.len()) as f64;
+        assert!((var / 2.25 - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_gen_gaussian_blobs_panic() {
+        rng(179).gen_gaussian_blobs(&[[0.0, 0.0]], -1.0, 1);
     }
 
     #[test]
     #[cfg(feature = "alloc")]
-    fn test_rng_boxed_trait() {
-        use crate::distributions::{Distribution, Standard};
-        let rng = rng(110);
-        let mut r = Box::new(rng) as Box<dyn RngCore>;
-        r.next_u32();
-        r.gen::<i32>();
-        assert_eq!(r.gen_range(0..1), 0);
-        let _c: u8 = Standard.sample(&mut r);
+    fn test_gen_random_tree() {
+        fn find(parent: &mut [usize], x: usize) -> usize {
+            let mut root = x;
+            while parent[root] != root {
+                root = parent[root];
+            }
+            parent[x] = root;
+            root
+        }
+
+        let mut r = rng(180);
+        assert!(r.gen_random_tree(0).is_empty());
+        assert!(r.gen_random_tree(1).is_empty());
+        assert_eq!(r.gen_random_tree(2), [(0, 1)]);
+
+        for &n in [3, 10, 100, 1000].iter() {
+            let edges = r.gen_random_tree(n);
+            assert_eq!(edges.len(), n - 1);
+            let mut normalized: Vec<_> = edges.iter().map(|&(a, b)| (a.min(b), a.max(b))).collect();
+            normalized.sort_unstable();
+            normalized.dedup();
+            assert_eq!(normalized.len(), n - 1);
+
+            // n - 1 edges, each joining two components, form a spanning tree
+            let mut parent: Vec<usize> = (0..n).collect();
+            for &(a, b) in edges.iter() {
+                assert!(a < n && b < n && a != b);
+                let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+                assert_ne!(ra, rb);
+                parent[ra] = rb;
+            }
+        }
+
+        // All 3^(3-2) = 3 trees on 3 nodes are equally likely: the tree is
+        // determined by its center node
+        let mut counts = [0i32; 3];
+        for _ in 0..3000 {
+            let edges = r.gen_random_tree(3);
+            let mut degree = [0; 3];
+            for &(a, b) in edges.iter() {
+                degree[a] += 1;
+                degree[b] += 1;
+            }
+            counts[degree.iter().position(|&d| d == 2).unwrap()] += 1;
+        }
+        assert!(counts.iter().all(|&c| (c - 1000).abs() < 100));
     }
 
     #[test]
-    #[cfg_attr(miri, ignore)] // Miri is too slow
-    fn test_gen_ratio_average() {
-        const NUM: u32 = 3;
-        const DENOM: u32 = 10;
-        const N: u32 = 100_000;
+    #[cfg(feature = "alloc")]
+    fn test_gen_stratified_2d() {
+        let mut r = rng(184);
+        assert!(r.gen_stratified_2d(0, 5).is_empty());
+        assert!(r.gen_stratified_2d(5, 0).is_empty());
 
-        let mut sum: u32 = 0;
-        let mut rng = rng(111);
+        let (grid_x, grid_y) = (7, 4);
+        let points = r.gen_stratified_2d(grid_x, grid_y);
+        assert_eq!(points.len(), grid_x * grid_y);
+        let mut covered = [[false; 7]; 4];
+        for (index, p) in points.iter().enumerate() {
+            let i = (p[0] * grid_x as f64) as usize;
+            let j = (p[1] * grid_y as f64) as usize;
+            assert_eq!(index, j * grid_x + i);
+            covered[j][i] = true;
+        }
+        assert!(covered.iter().all(|row| row.iter().all(|&c| c)));
+
+        // Points are uniform within their cell
+        let mut sum = [0.0; 2];
+        for _ in 0..1000 {
+            for p in r.gen_stratified_2d(2, 2).iter() {
+                sum[0] += (p[0] * 2.0) % 1.0;
+                sum[1] += (p[1] * 2.0) % 1.0;
+            }
+        }
+        assert!((sum[0] / 4000.0 - 0.5).abs() < 0.02);
+        assert!((sum[1] / 4000.0 - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_coupon_collector_steps() {
+        let mut r = rng(185);
+        assert_eq!(r.coupon_collector_steps(0), 0);
+        assert_eq!(r.coupon_collector_steps(1), 1);
+
+        const N: u32 = 20;
+        const TRIALS: u64 = 2000;
+        let mut total = 0;
+        for _ in 0..TRIALS {
+            let steps = r.coupon_collector_steps(N);
+            assert!(steps >= N as u64);
+            total += steps;
+        }
+        let harmonic: f64 = (1..=N).map(|k| 1.0 / k as f64).sum();
+        let expected = N as f64 * harmonic;
+        let mean = total as f64 / TRIALS as f64;
+        assert!((mean - expected).abs() < 0.05 * expected);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_gen_permutation_fixed_points() {
+        let mut r = rng(186);
+        for &(n, k) in &[(0, 0), (1, 1), (2, 0), (5, 5), (6, 0), (10, 3), (20, 18)] {
+            for _ in 0..20 {
+                let perm = r.gen_permutation_fixed_points(n, k);
+                let mut sorted = perm.clone();
+                sorted.sort_unstable();
+                assert!(sorted.iter().enumerate().all(|(i, &x)| x as usize == i));
+                let fixed = perm.iter().enumerate().filter(|&(i, &x)| x as usize == i).count();
+                assert_eq!(fixed, k);
+            }
+        }
+
+        // Both derangements of three elements occur
+        let mut seen = [false; 2];
+        for _ in 0..100 {
+            let perm = r.gen_permutation_fixed_points(3, 0);
+            seen[(perm[0] - 1) as usize] = true;
+        }
+        assert_eq!(seen, [true, true]);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "alloc")]
+    fn test_gen_permutation_fixed_points_panic() {
+        rng(187).gen_permutation_fixed_points(4, 3);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_gen_ar1() {
+        let mut r = rng(188);
+        assert!(r.gen_ar1(0, 0.5, 1.0).is_empty());
+        assert_eq!(r.gen_ar1(5, 0.5, 0.0), [0.0; 5]);
+
+        const N: usize = 100_000;
+        for &(phi, sigma) in &[(0.0, 1.0), (0.7, 2.0), (-0.5, 0.5)] {
+            let series = r.gen_ar1(N, phi, sigma);
+            assert_eq!(series.len(), N);
+            let n = N as f64;
+            let mean = series.iter().sum::<f64>() / n;
+            let var = series.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+            let cov = series
+                .windows(2)
+                .map(|w| (w[0] - mean) * (w[1] - mean))
+                .sum::<f64>()
+                / (n - 1.0);
+            let expected_var = sigma * sigma / (1.0 - phi * phi);
+            assert!((var - expected_var).abs() < 0.05 * expected_var);
+            assert!((cov / var - phi).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_gen_ar1_panic() {
+        rng(189).gen_ar1(10, 1.0, 1.0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_bootstrap() {
+        let mut r = rng(190);
+        let empty: [u8; 0] = [];
+        assert!(r.bootstrap(&empty, 10).is_empty());
+        assert!(r.bootstrap(&[1, 2, 3], 0).is_empty());
+
+        const N: usize = 2000;
+        let data = [10, 20, 30, 40, 50];
+        let mut counts = [0usize; 5];
         for _ in 0..N {
-            if rng.gen_ratio(NUM, DENOM) {
-                sum += 1;
+            let resample = r.bootstrap(&data, 5);
+            assert_eq!(resample.len(), 5);
+            for &x in resample.iter() {
+                let i = data.iter().position(|y| y == x).unwrap();
+                // The result references the original elements
+                assert!(core::ptr::eq(x, &data[i]));
+                counts[i] += 1;
             }
         }
-        // Have Binomial(N, NUM/DENOM) distribution
-        let expected = (NUM * N) / DENOM; // exact integer
-        assert!(((sum - expected) as i32).abs() < 500);
+        for &count in counts.iter() {
+            assert!((count as f64 / N as f64 - 1.0).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_choose_weighted_fn() {
+        use crate::distributions::WeightedIndex;
+
+        let mut r = rng(191);
+        assert_eq!(r.choose_weighted_fn(0, |_| 1.0), None);
+        assert_eq!(r.choose_weighted_fn(5, |_| 0.0), None);
+        assert_eq!(r.choose_weighted_fn(5, |i| (i == 3) as u8 as f64), Some(3));
+
+        const N: usize = 50_000;
+        let weight = |i: usize| [1.0, 0.0, 2.5, 0.5, 4.0, 0.0][i];
+        let distr = WeightedIndex::new((0..6).map(weight)).unwrap();
+        let mut counts = [0usize; 6];
+        let mut counts_index = [0usize; 6];
+        for _ in 0..N {
+            counts[r.choose_weighted_fn(6, weight).unwrap()] += 1;
+            counts_index[r.sample(&distr)] += 1;
+        }
+        assert_eq!((counts[1], counts[5]), (0, 0));
+        for (&a, &b) in counts.iter().zip(counts_index.iter()) {
+            assert!((a as f64 - b as f64).abs() < 0.015 * N as f64);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_choose_weighted_fn_panic() {
+        rng(192).choose_weighted_fn(3, |i| 1.0 - i as f64);
     }
 }
@@ -0,0 +1,145 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An RNG producing antithetic pairs of values.
+
+use rand_core::{impls, Error, RngCore};
+
+/// An RNG which follows each value of the inner generator with its
+/// complement, yielding antithetic pairs for Monte Carlo variance reduction.
+///
+/// Calls to [`next_u64`] alternate between drawing a fresh value `v` from
+/// the inner generator and returning its bitwise complement `!v` (that is,
+/// `u64::MAX - v`). All other methods are implemented in terms of
+/// [`next_u64`]: [`next_u32`] truncates a single `u64` (hence also returns
+/// complementary pairs) and [`fill_bytes`] consumes whole `u64` values.
+///
+/// Consequently, consecutive samples of uniform floats via [`Standard`] sum
+/// to (almost exactly) 1, and consecutive samples of monotone functions of
+/// those are negatively correlated. Averaging over such pairs can reduce
+/// the variance of Monte Carlo estimates compared to independent samples.
+///
+/// Half of the output is a deterministic function of the other half, so
+/// this is **not** suitable for cryptography or as a general-purpose RNG.
+///
+/// # Example
+///
+/// ```
+/// use rand::{Rng, SeedableRng};
+/// use rand::rngs::StdRng;
+/// use rand::rngs::adapter::Antithetic;
+///
+/// let mut rng = Antithetic::new(StdRng::seed_from_u64(1));
+/// let (u, v): (f64, f64) = (rng.gen(), rng.gen());
+/// assert!((u + v - 1.0).abs() < 1e-15);
+/// ```
+///
+/// [`next_u32`]: RngCore::next_u32
+/// [`next_u64`]: RngCore::next_u64
+/// [`fill_bytes`]: RngCore::fill_bytes
+/// [`Standard`]: crate::distributions::Standard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Antithetic<R> {
+    rng: R,
+    pending: Option<u64>,
+}
+
+impl<R: RngCore> Antithetic<R> {
+    /// Create a new `Antithetic` adapter around `rng`.
+    pub fn new(rng: R) -> Antithetic<R> {
+        Antithetic { rng, pending: None }
+    }
+
+    /// Consume self, returning the inner generator.
+    ///
+    /// If the last value returned was the first of a pair, its complement is
+    /// discarded.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+impl<R: RngCore> RngCore for Antithetic<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self.pending.take() {
+            Some(x) => x,
+            None => {
+                let x = self.rng.next_u64();
+                self.pending = Some(!x);
+                x
+            }
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Antithetic;
+    use crate::rngs::mock::StepRng;
+    use crate::{Rng, RngCore};
+
+    #[test]
+    fn test_antithetic() {
+        let mut rng = Antithetic::new(StepRng::new(5, 1));
+        assert_eq!(rng.next_u64(), 5);
+        assert_eq!(rng.next_u64(), !5);
+        assert_eq!(rng.next_u32(), 6);
+        assert_eq!(rng.next_u32(), !6);
+        assert_eq!(rng.into_inner().next_u64(), 7);
+
+        let mut rng = Antithetic::new(crate::test::rng(420));
+        for _ in 0..1000 {
+            let (u, v): (f64, f64) = (rng.gen(), rng.gen());
+            assert!((u + v - 1.0).abs() < 1e-15);
+            let (u, v): (f32, f32) = (rng.gen(), rng.gen());
+            assert!((u + v - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_antithetic_variance_reduction() {
+        // Estimate E[U^2] = 1/3 from PAIRS pairs, many times over, and
+        // compare the variance of the estimates
+        const PAIRS: usize = 10;
+        const REPS: usize = 2000;
+        fn estimate_variance<R: Rng>(rng: &mut R) -> f64 {
+            let (mut sum, mut sum_sq) = (0.0, 0.0);
+            for _ in 0..REPS {
+                let mut mean = 0.0;
+                for _ in 0..2 * PAIRS {
+                    let u: f64 = rng.gen();
+                    mean += u * u;
+                }
+                mean /= (2 * PAIRS) as f64;
+                sum += mean;
+                sum_sq += mean * mean;
+            }
+            let mean = sum / REPS as f64;
+            assert!((mean - 1.0 / 3.0).abs() < 0.01);
+            sum_sq / REPS as f64 - mean * mean
+        }
+
+        let independent = estimate_variance(&mut crate::test::rng(421));
+        let antithetic = estimate_variance(&mut Antithetic::new(crate::test::rng(422)));
+        // The variance per pair is 4/45 independently, 1/180 antithetically
+        assert!(antithetic < independent / 5.0);
+    }
+}
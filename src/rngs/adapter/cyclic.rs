@@ -0,0 +1,121 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An RNG repeating a fixed buffer of bytes.
+
+use std::vec::Vec;
+
+use rand_core::{impls, Error, RngCore};
+
+/// An RNG that yields the bytes of a fixed buffer, wrapping around to the
+/// start when the end is reached.
+///
+/// Unlike [`ReadRng`], this never runs out of data and never returns an
+/// error, making it convenient for deterministic tests. It is of course not
+/// random at all.
+///
+/// Words are read from the buffer in little-endian order, hence
+/// [`next_u32`] and [`next_u64`] consume 4 and 8 bytes respectively.
+///
+/// # Example
+///
+/// ```
+/// use rand::RngCore;
+/// use rand::rngs::adapter::CyclicRng;
+///
+/// let mut rng = CyclicRng::new(vec![1, 2, 3]);
+/// let mut buf = [0u8; 5];
+/// rng.fill_bytes(&mut buf);
+/// assert_eq!(buf, [1, 2, 3, 1, 2]);
+/// ```
+///
+/// [`ReadRng`]: crate::rngs::adapter::ReadRng
+/// [`next_u32`]: RngCore::next_u32
+/// [`next_u64`]: RngCore::next_u64
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CyclicRng {
+    bytes: Vec<u8>,
+    pos: usize,
+}
+
+impl CyclicRng {
+    /// Create a new `CyclicRng` repeating `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// If `bytes` is empty.
+    pub fn new(bytes: Vec<u8>) -> CyclicRng {
+        assert!(!bytes.is_empty(), "CyclicRng::new called with empty buffer");
+        CyclicRng { bytes, pos: 0 }
+    }
+}
+
+impl RngCore for CyclicRng {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            let available = &self.bytes[self.pos..];
+            let n = available.len().min(dest.len() - filled);
+            dest[filled..filled + n].copy_from_slice(&available[..n]);
+            filled += n;
+            self.pos = (self.pos + n) % self.bytes.len();
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec;
+
+    use super::CyclicRng;
+    use crate::RngCore;
+
+    #[test]
+    fn test_cyclic_rng() {
+        let mut rng = CyclicRng::new(vec![1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+        assert_eq!(rng.next_u32(), 1);
+        assert_eq!(rng.next_u64(), (3 << 32) + 2);
+        // Wrapped around to the start
+        assert_eq!(rng.next_u32(), 1);
+
+        let mut buf = [0u8; 30];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(&buf[..8], &[2, 0, 0, 0, 3, 0, 0, 0]);
+        assert_eq!(&buf[8..20], &[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+        assert_eq!(&buf[20..], &[1, 0, 0, 0, 2, 0, 0, 0, 3, 0]);
+        assert_eq!(rng.next_u32(), 0x01_00_00);
+    }
+
+    #[test]
+    fn test_cyclic_rng_single_byte() {
+        let mut rng = CyclicRng::new(vec![7]);
+        assert_eq!(rng.next_u32(), 0x07070707);
+        let mut buf = [0u8; 3];
+        assert!(rng.try_fill_bytes(&mut buf).is_ok());
+        assert_eq!(buf, [7; 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cyclic_rng_empty() {
+        CyclicRng::new(vec![]);
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An RNG alternating between two inner RNGs.
+
+use rand_core::{impls, Error, RngCore};
+
+/// An RNG which alternates between two inner generators.
+///
+/// Each call to [`next_u64`] draws from `a` on even-numbered calls (starting
+/// with the first, call 0) and from `b` on odd-numbered calls. All other
+/// methods are implemented in terms of [`next_u64`]: [`next_u32`] truncates a
+/// single `u64` and [`fill_bytes`] consumes whole `u64` values, thus the
+/// output is fully determined by the two inner generators.
+///
+/// This is mainly a building block for combining reproducible streams.
+///
+/// # Example
+///
+/// ```
+/// use rand::RngCore;
+/// use rand::rngs::mock::StepRng;
+/// use rand::rngs::adapter::InterleavedRng;
+///
+/// let mut rng = InterleavedRng::new(StepRng::new(0, 1), StepRng::new(100, 1));
+/// let v: Vec<u64> = (0..4).map(|_| rng.next_u64()).collect();
+/// assert_eq!(v, [0, 100, 1, 101]);
+/// ```
+///
+/// [`next_u32`]: RngCore::next_u32
+/// [`next_u64`]: RngCore::next_u64
+/// [`fill_bytes`]: RngCore::fill_bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterleavedRng<A, B> {
+    a: A,
+    b: B,
+    next_is_b: bool,
+}
+
+impl<A: RngCore, B: RngCore> InterleavedRng<A, B> {
+    /// Create a new `InterleavedRng`, starting with `a`.
+    pub fn new(a: A, b: B) -> InterleavedRng<A, B> {
+        InterleavedRng { a, b, next_is_b: false }
+    }
+
+    /// Consume self, returning the two inner generators.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A: RngCore, B: RngCore> RngCore for InterleavedRng<A, B> {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let x = if self.next_is_b {
+            self.b.next_u64()
+        } else {
+            self.a.next_u64()
+        };
+        self.next_is_b = !self.next_is_b;
+        x
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InterleavedRng;
+    use crate::rngs::mock::StepRng;
+    use crate::{RngCore, SeedableRng};
+
+    #[test]
+    fn test_interleaved_rng() {
+        let a = rand_pcg::Pcg32::seed_from_u64(1);
+        let b = rand_pcg::Pcg32::seed_from_u64(2);
+        let mut rng = InterleavedRng::new(a.clone(), b.clone());
+        let mut rng2 = rng.clone();
+
+        let (mut a, mut b) = (a, b);
+        for _ in 0..10 {
+            assert_eq!(rng.next_u64(), a.next_u64());
+            assert_eq!(rng.next_u64(), b.next_u64());
+        }
+        assert_eq!(rng.into_inner(), (a, b));
+
+        // Reproducible given identically seeded inputs
+        let mut rng3 = InterleavedRng::new(
+            rand_pcg::Pcg32::seed_from_u64(1),
+            rand_pcg::Pcg32::seed_from_u64(2),
+        );
+        for _ in 0..10 {
+            assert_eq!(rng2.next_u64(), rng3.next_u64());
+        }
+        let mut buf2 = [0u8; 20];
+        let mut buf3 = [0u8; 20];
+        rng2.fill_bytes(&mut buf2);
+        rng3.fill_bytes(&mut buf3);
+        assert_eq!(buf2, buf3);
+    }
+
+    #[test]
+    fn test_interleaved_rng_other_methods() {
+        let mut rng = InterleavedRng::new(StepRng::new(1, 2), StepRng::new(2, 2));
+        assert_eq!(rng.next_u32(), 1);
+        assert_eq!(rng.next_u32(), 2);
+        let mut buf = [0u8; 12];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [3, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0]);
+        assert_eq!(rng.next_u64(), 5);
+        assert_eq!(rng.next_u64(), 6);
+    }
+}
@@ -8,9 +8,19 @@
 
 //! Wrappers / adapters forming RNGs
 
+mod antithetic;
+mod cyclic;
+mod interleaved;
 mod read;
+mod record_replay;
 mod reseeding;
+mod traced;
 
+pub use self::antithetic::Antithetic;
+pub use self::cyclic::CyclicRng;
+pub use self::interleaved::InterleavedRng;
 #[allow(deprecated)]
 pub use self::read::{ReadError, ReadRng};
+pub use self::record_replay::RecordReplay;
 pub use self::reseeding::ReseedingRng;
+pub use self::traced::Traced;
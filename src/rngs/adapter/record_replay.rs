@@ -0,0 +1,171 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper recording the output of an RNG for later replay.
+
+use std::vec::Vec;
+
+use rand_core::{impls, Error, RngCore};
+
+/// An RNG which either records all output of an inner RNG, or replays a
+/// previous recording.
+///
+/// In record mode (see [`RecordReplay::record`]) every word drawn from the
+/// inner RNG is appended to a log. In replay mode (see
+/// [`RecordReplay::replay`]) the words of a log are returned again, in
+/// order. This allows capturing the randomness of a failing run, for example
+/// in a randomized test, and replaying it deterministically while debugging.
+///
+/// All output is derived from `u64` words: [`next_u32`] truncates a single
+/// word and [`fill_bytes`] consumes whole words. Hence recording changes the
+/// stream compared to using the inner RNG directly, but a replay always
+/// matches its recording exactly.
+///
+/// # Panics
+///
+/// Generating values in replay mode panics when the log is exhausted.
+///
+/// # Example
+///
+/// ```
+/// use rand::{thread_rng, Rng};
+/// use rand::rngs::ThreadRng;
+/// use rand::rngs::adapter::RecordReplay;
+///
+/// let mut rng = RecordReplay::record(thread_rng());
+/// let x: f64 = rng.gen();
+/// let log = rng.into_log();
+///
+/// let mut rng = RecordReplay::<ThreadRng>::replay(log);
+/// assert_eq!(rng.gen::<f64>(), x);
+/// ```
+///
+/// [`next_u32`]: RngCore::next_u32
+/// [`fill_bytes`]: RngCore::fill_bytes
+#[derive(Debug, Clone)]
+pub struct RecordReplay<R> {
+    // The inner RNG in record mode, `None` in replay mode
+    rng: Option<R>,
+    log: Vec<u64>,
+    // Position of the next word to replay
+    pos: usize,
+}
+
+impl<R: RngCore> RecordReplay<R> {
+    /// Create a new `RecordReplay` in record mode, drawing from `rng`.
+    pub fn record(rng: R) -> RecordReplay<R> {
+        RecordReplay { rng: Some(rng), log: Vec::new(), pos: 0 }
+    }
+
+    /// Create a new `RecordReplay` in replay mode, returning the words of
+    /// `log`.
+    pub fn replay(log: Vec<u64>) -> RecordReplay<R> {
+        RecordReplay { rng: None, log, pos: 0 }
+    }
+
+    /// Return `true` if in record mode.
+    pub fn is_recording(&self) -> bool {
+        self.rng.is_some()
+    }
+
+    /// The log: in record mode all words recorded so far, in replay mode
+    /// all words being replayed.
+    pub fn log(&self) -> &[u64] {
+        &self.log
+    }
+
+    /// Consume self, returning the log.
+    pub fn into_log(self) -> Vec<u64> {
+        self.log
+    }
+}
+
+impl<R: RngCore> RngCore for RecordReplay<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self.rng {
+            Some(ref mut rng) => {
+                let x = rng.next_u64();
+                self.log.push(x);
+                x
+            }
+            None => {
+                let x = *self.log.get(self.pos).unwrap_or_else(|| {
+                    panic!("RecordReplay: replay log exhausted after {} words", self.pos)
+                });
+                self.pos += 1;
+                x
+            }
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec;
+    use std::vec::Vec;
+
+    use super::RecordReplay;
+    use crate::rngs::mock::StepRng;
+    use crate::seq::SliceRandom;
+    use crate::{Rng, RngCore, SeedableRng};
+
+    fn computation<R: Rng>(rng: &mut R) -> (Vec<u32>, f64, bool, [u8; 11]) {
+        let mut v: Vec<u32> = (0..20).collect();
+        v.shuffle(rng);
+        let mut bytes = [0u8; 11];
+        rng.fill(&mut bytes);
+        (v, rng.gen_range(-1.0..1.0), rng.gen_bool(0.3), bytes)
+    }
+
+    #[test]
+    fn test_record_replay() {
+        let mut rng = RecordReplay::record(rand_pcg::Pcg32::seed_from_u64(1));
+        assert!(rng.is_recording());
+        let recorded = computation(&mut rng);
+        let log = rng.into_log();
+        assert!(!log.is_empty());
+
+        let mut rng = RecordReplay::<rand_pcg::Pcg32>::replay(log.clone());
+        assert!(!rng.is_recording());
+        assert_eq!(computation(&mut rng), recorded);
+        assert_eq!(rng.log(), &log[..]);
+    }
+
+    #[test]
+    fn test_record_replay_words() {
+        let mut rng = RecordReplay::record(StepRng::new(1, 1));
+        assert_eq!(rng.next_u32(), 1);
+        assert_eq!(rng.next_u64(), 2);
+        assert_eq!(rng.log(), [1, 2]);
+
+        let mut rng = RecordReplay::<StepRng>::replay(vec![1, 2]);
+        assert_eq!(rng.next_u64(), 1);
+        assert_eq!(rng.next_u32(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_record_replay_exhausted() {
+        let mut rng = RecordReplay::<StepRng>::replay(vec![1]);
+        rng.next_u64();
+        rng.next_u64();
+    }
+}
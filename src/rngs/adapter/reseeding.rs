@@ -100,6 +100,35 @@ where
     pub fn reseed(&mut self) -> Result<(), Error> {
         self.0.core.reseed()
     }
+
+    /// Get a reference to the wrapped PRNG.
+    pub fn inner(&self) -> &R {
+        &self.0.core.inner
+    }
+
+    /// Get a mutable reference to the wrapped PRNG.
+    ///
+    /// Note that results already generated into the buffer of this wrapper
+    /// are unaffected by changes to the wrapped PRNG.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.0.core.inner
+    }
+
+    /// Get a reference to the reseeder.
+    pub fn reseeder(&self) -> &Rsdr {
+        &self.0.core.reseeder
+    }
+
+    /// Consume the wrapper, returning the wrapped PRNG and the reseeder.
+    ///
+    /// Any results which were generated but not yet consumed are discarded:
+    /// the wrapped PRNG continues with the block following the buffered one.
+    /// Its output therefore only continues that of the wrapper when the
+    /// buffer has been used up.
+    pub fn into_inner(self) -> (R, Rsdr) {
+        let core = self.0.core;
+        (core.inner, core.reseeder)
+    }
 }
 
 // TODO: this should be implemented for any type where the inner type
@@ -369,4 +398,26 @@ mod test {
         let mut rng2 = rng1.clone();
         assert_eq!(first, rng2.gen::<u32>());
     }
+
+    #[test]
+    fn test_reseeding_into_inner() {
+        use rand_core::block::BlockRng;
+        use rand_core::RngCore;
+
+        let mut zero = StepRng::new(0, 0);
+        let rng = Core::from_rng(&mut zero).unwrap();
+        let mut rng1 = ReseedingRng::new(rng.clone(), 0, zero);
+        let mut rng2 = ReseedingRng::new(rng, 0, StepRng::new(1, 1));
+        assert_eq!(rng2.reseeder().clone().next_u64(), 1);
+
+        // Use up the first block of results (`[u32; 64]`)
+        for _ in 0..64 {
+            assert_eq!(rng1.next_u32(), rng2.next_u32());
+        }
+        let (inner, _) = rng2.into_inner();
+        let mut inner = BlockRng::new(inner);
+        for _ in 0..100 {
+            assert_eq!(rng1.next_u32(), inner.next_u32());
+        }
+    }
 }
@@ -0,0 +1,135 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper recording recent outputs of an RNG.
+
+use std::vec::Vec;
+
+use rand_core::{Error, RngCore};
+
+/// A wrapper around any RNG which records the most recent outputs of
+/// [`next_u64`], for debugging.
+///
+/// When a distribution produces a surprising value, [`Traced::recent`] shows
+/// exactly which words were consumed to produce it. Up to `capacity` words
+/// are kept, oldest first. Only [`next_u64`] is traced: [`next_u32`] and
+/// [`fill_bytes`] are passed through to the wrapped RNG unrecorded.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::rngs::adapter::Traced;
+/// use rand::rngs::mock::StepRng;
+///
+/// let mut rng = Traced::new(StepRng::new(3, 1), 4);
+/// let _: u64 = rng.gen();
+/// let _: u64 = rng.gen();
+/// assert_eq!(rng.recent(), [3, 4]);
+/// ```
+///
+/// [`next_u64`]: RngCore::next_u64
+/// [`next_u32`]: RngCore::next_u32
+/// [`fill_bytes`]: RngCore::fill_bytes
+#[derive(Debug, Clone)]
+pub struct Traced<R> {
+    rng: R,
+    // Holds up to `2 * capacity` words; the last `capacity` are reported.
+    // Discarding old words in bulk keeps recording amortized O(1).
+    trace: Vec<u64>,
+    capacity: usize,
+}
+
+impl<R: RngCore> Traced<R> {
+    /// Create a new `Traced` wrapping `rng` and recording the most recent
+    /// `capacity` outputs of `next_u64`.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity == 0`.
+    pub fn new(rng: R, capacity: usize) -> Traced<R> {
+        assert!(capacity > 0, "Traced::new called with zero capacity");
+        Traced {
+            rng,
+            trace: Vec::with_capacity(2 * capacity),
+            capacity,
+        }
+    }
+
+    /// The most recent outputs of `next_u64`, oldest first.
+    pub fn recent(&self) -> &[u64] {
+        let start = self.trace.len().saturating_sub(self.capacity);
+        &self.trace[start..]
+    }
+
+    /// Forget all recorded outputs.
+    pub fn clear_trace(&mut self) {
+        self.trace.clear();
+    }
+}
+
+impl<R: RngCore> RngCore for Traced<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value = self.rng.next_u64();
+        if self.trace.len() == 2 * self.capacity {
+            self.trace.drain(..self.capacity);
+        }
+        self.trace.push(value);
+        value
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Traced;
+    use crate::rngs::mock::StepRng;
+    use crate::{Rng, RngCore};
+
+    #[test]
+    fn test_traced() {
+        let mut rng = Traced::new(StepRng::new(5, 1), 3);
+        assert!(rng.recent().is_empty());
+
+        let x = rng.gen_range(0..10u64);
+        assert!(x < 10);
+        assert_eq!(rng.recent(), [5]);
+
+        for _ in 0..10 {
+            rng.next_u64();
+        }
+        assert_eq!(rng.recent(), [13, 14, 15]);
+
+        // Other methods are not traced
+        rng.next_u32();
+        rng.fill_bytes(&mut [0u8; 16]);
+        assert_eq!(rng.recent(), [13, 14, 15]);
+
+        rng.clear_trace();
+        assert!(rng.recent().is_empty());
+        assert_eq!(rng.next_u64(), 19);
+        assert_eq!(rng.recent(), [19]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_traced_zero_capacity() {
+        Traced::new(StepRng::new(0, 1), 0);
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A reproducible RNG for `no_std` targets
+
+use rand_core::{Error, RngCore, SeedableRng};
+use rand_pcg::Pcg32;
+
+/// A small, reproducible PRNG for `no_std` targets, seeded from a
+/// user-provided entropy source.
+///
+/// Embedded targets often have a hardware random number generator but no
+/// operating system to provide [`OsRng`]. `CoreRng` is seeded once from such
+/// a source via [`CoreRng::try_new_from_hardware`], which reports failure of
+/// the source as an error instead of panicking.
+///
+/// The algorithm is pinned to PCG32 (`Lcg64Xsh32` from the [rand_pcg]
+/// crate) and will only change in a breaking release, so output from a given
+/// seed is reproducible. This generator is **not** cryptographically secure.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::rngs::CoreRng;
+///
+/// // Stand-in for reading a hardware RNG peripheral
+/// let read_hardware_rng = |buf: &mut [u8]| {
+///     buf.copy_from_slice(&[0x5a; 16]);
+///     Ok(())
+/// };
+/// let mut rng = CoreRng::try_new_from_hardware(read_hardware_rng).unwrap();
+/// let x: u8 = rng.gen_range(1..=6);
+/// ```
+///
+/// [`OsRng`]: rand_core::OsRng
+/// [rand_pcg]: https://crates.io/crates/rand_pcg
+#[cfg_attr(doc_cfg, doc(cfg(feature = "core_rng")))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoreRng(Pcg32);
+
+impl CoreRng {
+    /// Create a new `CoreRng`, seeded by a single call to `fill`.
+    ///
+    /// `fill` must fill the whole buffer passed to it with random bytes (the
+    /// buffer is currently 16 bytes long). Any error returned by `fill` is
+    /// passed on.
+    pub fn try_new_from_hardware<F>(mut fill: F) -> Result<CoreRng, Error>
+    where F: FnMut(&mut [u8]) -> Result<(), Error> {
+        let mut seed = <Self as SeedableRng>::Seed::default();
+        fill(seed.as_mut())?;
+        Ok(CoreRng::from_seed(seed))
+    }
+}
+
+impl RngCore for CoreRng {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    #[inline(always)]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest);
+    }
+
+    #[inline(always)]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl SeedableRng for CoreRng {
+    type Seed = <Pcg32 as SeedableRng>::Seed;
+
+    #[inline(always)]
+    fn from_seed(seed: Self::Seed) -> Self {
+        CoreRng(Pcg32::from_seed(seed))
+    }
+
+    #[inline(always)]
+    fn from_rng<R: RngCore>(rng: R) -> Result<Self, Error> {
+        Pcg32::from_rng(rng).map(CoreRng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CoreRng;
+    use crate::{Error, RngCore, SeedableRng};
+    use core::num::NonZeroU32;
+
+    #[test]
+    fn test_core_rng_from_hardware() {
+        let fill = |buf: &mut [u8]| {
+            for (i, b) in buf.iter_mut().enumerate() {
+                *b = i as u8;
+            }
+            Ok(())
+        };
+        let mut rng = CoreRng::try_new_from_hardware(fill).unwrap();
+        let mut seed = [0u8; 16];
+        fill(&mut seed).unwrap();
+        let mut rng2 = CoreRng::from_seed(seed);
+        assert_eq!(rng, rng2);
+
+        let v: [u32; 3] = [rng.next_u32(), rng.next_u32(), rng.next_u32()];
+        assert_eq!(v, [81210529, 3065052540, 4163272645]);
+        assert_eq!(rng2.next_u32(), v[0]);
+    }
+
+    #[test]
+    fn test_core_rng_hardware_failure() {
+        let code = NonZeroU32::new(Error::CUSTOM_START + 1).unwrap();
+        let result = CoreRng::try_new_from_hardware(|_| Err(Error::from(code)));
+        assert_eq!(result.unwrap_err().code(), Some(code));
+    }
+}
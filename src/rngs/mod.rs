@@ -64,6 +64,8 @@
 //!     periodic reseeding.
 //! -   [`SmallRng`] is an **insecure** PRNG designed to be fast, simple, require
 //!     little memory, and have good output quality.
+//! -   [`KeyedRng`] is an **insecure**, reproducible PRNG derived from a master
+//!     seed and a key, for procedural generation.
 //!
 //! The algorithms selected for [`StdRng`] and [`SmallRng`] may change in any
 //! release and may be platform-dependent, therefore they should be considered
@@ -102,18 +104,23 @@
 pub mod mock; // Public so we don't export `StepRng` directly, making it a bit
               // more clear it is intended for testing.
 
-#[cfg(all(feature = "small_rng", target_pointer_width = "64"))]
-mod xoshiro256plusplus;
+// Also used by `KeyedRng` and `default_rng` on all platforms
+#[cfg(any(feature = "small_rng", feature = "default_rng"))]
+pub(crate) mod xoshiro256plusplus;
 #[cfg(all(feature = "small_rng", not(target_pointer_width = "64")))]
 mod xoshiro128plusplus;
+#[cfg(feature = "small_rng")] mod keyed;
 #[cfg(feature = "small_rng")] mod small;
 
+#[cfg(feature = "core_rng")] mod core_rng;
 #[cfg(feature = "std_rng")] mod std;
 #[cfg(all(feature = "std", feature = "std_rng"))] pub(crate) mod thread;
 
+#[cfg(feature = "core_rng")] pub use self::core_rng::CoreRng;
+#[cfg(feature = "small_rng")] pub use self::keyed::KeyedRng;
 #[cfg(feature = "small_rng")] pub use self::small::SmallRng;
 #[cfg(feature = "std_rng")] pub use self::std::StdRng;
-#[cfg(all(feature = "std", feature = "std_rng"))] pub use self::thread::ThreadRng;
+#[cfg(all(feature = "std", feature = "std_rng"))] pub use self::thread::{CachedRng, EntropySource, ThreadRng, ThreadRngBuilder};
 
 #[cfg_attr(doc_cfg, doc(cfg(feature = "getrandom")))]
 #[cfg(feature = "getrandom")] pub use rand_core::OsRng;
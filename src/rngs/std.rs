@@ -75,6 +75,103 @@ impl SeedableRng for StdRng {
 
 impl CryptoRng for StdRng {}
 
+impl StdRng {
+    /// Create a new `StdRng` seeded from a hash of `data`.
+    ///
+    /// The input is absorbed into a sponge built on the ChaCha permutation
+    /// (20 rounds), and the squeezed 32-byte state is used as seed. The same
+    /// `data` always yields the same generator (within one version of this
+    /// library and platform, see above), while any change to `data` yields
+    /// an unrelated generator. This is useful for randomness keyed on input
+    /// data, for example the contents of a file in a deterministic build.
+    ///
+    /// This is not a general-purpose cryptographic hash function and no
+    /// guarantees are made about its security beyond the above.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::Rng;
+    ///
+    /// let data = b"contents of some input file";
+    /// let x: u64 = StdRng::seed_from_bytes_hashed(data).gen();
+    /// assert_eq!(StdRng::seed_from_bytes_hashed(data).gen::<u64>(), x);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    pub fn seed_from_bytes_hashed(data: &[u8]) -> StdRng {
+        let hashed = hash_bytes(data);
+        let mut seed = <Self as SeedableRng>::Seed::default();
+        seed.as_mut().copy_from_slice(&hashed);
+        StdRng::from_seed(seed)
+    }
+}
+
+/// Hash `data` to 32 bytes using a sponge on the ChaCha permutation.
+///
+/// The rate consists of the 8 key words of the ChaCha state, the capacity of
+/// the remaining 8 words. Input is padded with `0x80` followed by zeros.
+#[cfg(feature = "std")]
+fn hash_bytes(data: &[u8]) -> [u8; 32] {
+    const RATE: usize = 32;
+    let mut state: [u32; 16] = [
+        0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    let absorb = |state: &mut [u32; 16], block: &[u8]| {
+        for (i, word) in block.chunks(4).enumerate() {
+            let mut buf = [0u8; 4];
+            buf.copy_from_slice(word);
+            state[4 + i] ^= u32::from_le_bytes(buf);
+        }
+        chacha_permute(state);
+    };
+
+    let mut chunks = data.chunks_exact(RATE);
+    for block in &mut chunks {
+        absorb(&mut state, block);
+    }
+    let rest = chunks.remainder();
+    let mut last = [0u8; RATE];
+    last[..rest.len()].copy_from_slice(rest);
+    last[rest.len()] = 0x80;
+    absorb(&mut state, &last);
+
+    let mut out = [0u8; 32];
+    for (chunk, word) in out.chunks_mut(4).zip(state[4..12].iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    out
+}
+
+/// The ChaCha permutation with 20 rounds (without feed-forward).
+#[cfg(feature = "std")]
+fn chacha_permute(x: &mut [u32; 16]) {
+    fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+        x[a] = x[a].wrapping_add(x[b]);
+        x[d] = (x[d] ^ x[a]).rotate_left(16);
+        x[c] = x[c].wrapping_add(x[d]);
+        x[b] = (x[b] ^ x[c]).rotate_left(12);
+        x[a] = x[a].wrapping_add(x[b]);
+        x[d] = (x[d] ^ x[a]).rotate_left(8);
+        x[c] = x[c].wrapping_add(x[d]);
+        x[b] = (x[b] ^ x[c]).rotate_left(7);
+    }
+
+    for _ in 0..10 {
+        quarter_round(x, 0, 4, 8, 12);
+        quarter_round(x, 1, 5, 9, 13);
+        quarter_round(x, 2, 6, 10, 14);
+        quarter_round(x, 3, 7, 11, 15);
+        quarter_round(x, 0, 5, 10, 15);
+        quarter_round(x, 1, 6, 11, 12);
+        quarter_round(x, 2, 7, 8, 13);
+        quarter_round(x, 3, 4, 9, 14);
+    }
+}
+
 
 #[cfg(test)]
 mod test {
@@ -99,4 +196,47 @@ mod test {
 
         assert_eq!([x0, x1], target);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_seed_from_bytes_hashed() {
+        let mut data = [0u8; 100];
+        for (i, x) in data.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        let sample = |data: &[u8]| {
+            let mut rng = StdRng::seed_from_bytes_hashed(data);
+            let mut v = [0u64; 16];
+            for x in v.iter_mut() {
+                *x = rng.next_u64();
+            }
+            v
+        };
+        let bits_differing = |a: &[u64], b: &[u64]| {
+            a.iter().zip(b.iter()).map(|(x, y)| (x ^ y).count_ones()).sum::<u32>()
+        };
+
+        let a = sample(&data);
+        assert_eq!(a, sample(&data));
+
+        // Flipping a single bit anywhere (including in the final partial
+        // block) changes about half of all output bits
+        for &i in [0, 31, 32, 63, 64, 99].iter() {
+            let mut flipped = data;
+            flipped[i] ^= 1;
+            let b = sample(&flipped);
+            let diff = bits_differing(&a, &b);
+            assert!(diff > 400 && diff < 624, "{} bits differ", diff);
+
+            let h0 = super::hash_bytes(&data);
+            let h1 = super::hash_bytes(&flipped);
+            let diff = h0.iter().zip(h1.iter()).map(|(x, y)| (x ^ y).count_ones()).sum::<u32>();
+            assert!(diff > 88 && diff < 168, "{} bits differ", diff);
+        }
+
+        // Padding distinguishes inputs which differ only in trailing zeros
+        assert_ne!(sample(&data[..32]), sample(&data[..31]));
+        assert_ne!(sample(&[]), sample(&[0]));
+        assert_ne!(sample(&[0; 31]), sample(&[0; 32]));
+    }
 }
@@ -8,7 +8,8 @@
 
 //! Thread-local random number generator
 
-use core::cell::UnsafeCell;
+use core::cell::{Cell, RefCell, UnsafeCell};
+use core::marker::PhantomData;
 use std::rc::Rc;
 use std::thread_local;
 
@@ -16,6 +17,7 @@ use super::std::Core;
 use crate::rngs::adapter::ReseedingRng;
 use crate::rngs::OsRng;
 use crate::{CryptoRng, Error, RngCore, SeedableRng};
+use rand_core::block::BlockRngCore;
 
 // Rationale for using `UnsafeCell` in `ThreadRng`:
 //
@@ -54,6 +56,10 @@ const THREAD_RNG_RESEED_THRESHOLD: u64 = 1024 * 64;
 /// attacks and mis-use (e.g. if somehow weak entropy were supplied initially).
 /// The PRNG algorithms used are assumed to be secure.
 ///
+/// **Warning:** if the `testing` feature of this crate is enabled, any code
+/// in the process may call `set_global_test_seed`, making `ThreadRng`
+/// completely predictable. Never enable that feature outside of tests.
+///
 /// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
 /// [`StdRng`]: crate::rngs::StdRng
 #[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
@@ -67,15 +73,136 @@ thread_local!(
     // We require Rc<..> to avoid premature freeing when thread_rng is used
     // within thread-local destructors. See #968.
     static THREAD_RNG_KEY: Rc<UnsafeCell<ReseedingRng<Core, OsRng>>> = {
-        let r = Core::from_rng(OsRng).unwrap_or_else(|err|
-                panic!("could not initialize thread_rng: {}", err));
-        let rng = ReseedingRng::new(r,
-                                    THREAD_RNG_RESEED_THRESHOLD,
-                                    OsRng);
-        Rc::new(UnsafeCell::new(rng))
-    }
+        Rc::new(UnsafeCell::new(new_thread_rng()))
+    };
+
+    // The source used to seed the generator of `THREAD_RNG_KEY`, recorded by
+    // `new_thread_rng`
+    #[allow(clippy::missing_const_for_thread_local)] // requires Rust 1.59
+    static THREAD_RNG_SOURCE: Cell<Option<EntropySource>> = Cell::new(None);
 );
 
+fn new_thread_rng() -> ReseedingRng<Core, OsRng> {
+    #[cfg(feature = "testing")]
+    {
+        if let Some((r, seed, thread_index)) = testing::seeded_core() {
+            THREAD_RNG_SOURCE.with(|source| {
+                source.set(Some(EntropySource::TestSeed { seed, thread_index }))
+            });
+            // Never reseed based on the number of generated bytes, to keep
+            // the output reproducible
+            return ReseedingRng::new(r, 0, OsRng);
+        }
+    }
+    let r = Core::from_rng(OsRng).unwrap_or_else(|err|
+            panic!("could not initialize thread_rng: {}", err));
+    THREAD_RNG_SOURCE.with(|source| source.set(Some(EntropySource::Os)));
+    ReseedingRng::new(r, THREAD_RNG_RESEED_THRESHOLD, OsRng)
+}
+
+#[cfg(feature = "testing")]
+mod testing {
+    // Only 32-bit atomics are used, since some targets lack `AtomicU64`
+    use core::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+
+    use super::Core;
+    use crate::SeedableRng;
+
+    // Guards the other statics, such that the seed is never read while
+    // partially written
+    static LOCK: AtomicBool = AtomicBool::new(false);
+    static SEED_LO: AtomicU32 = AtomicU32::new(0);
+    static SEED_HI: AtomicU32 = AtomicU32::new(0);
+    static SEED_SET: AtomicBool = AtomicBool::new(false);
+    static NEXT_THREAD_INDEX: AtomicUsize = AtomicUsize::new(0);
+
+    fn with_lock<T, F: FnOnce() -> T>(f: F) -> T {
+        while LOCK
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            std::thread::yield_now();
+        }
+        let result = f();
+        LOCK.store(false, Ordering::Release);
+        result
+    }
+
+    pub(super) fn set_seed(seed: u64) {
+        with_lock(|| {
+            SEED_LO.store(seed as u32, Ordering::Relaxed);
+            SEED_HI.store((seed >> 32) as u32, Ordering::Relaxed);
+            NEXT_THREAD_INDEX.store(0, Ordering::Relaxed);
+            SEED_SET.store(true, Ordering::Relaxed);
+        })
+    }
+
+    /// Construct the core for a newly initialized thread, if a global seed
+    /// was set, along with the seed and the thread's index.
+    pub(super) fn seeded_core() -> Option<(Core, u64, usize)> {
+        let (seed, index) = with_lock(|| {
+            if !SEED_SET.load(Ordering::Relaxed) {
+                return None;
+            }
+            let seed = u64::from(SEED_LO.load(Ordering::Relaxed))
+                | u64::from(SEED_HI.load(Ordering::Relaxed)) << 32;
+            let index = NEXT_THREAD_INDEX.fetch_add(1, Ordering::Relaxed);
+            Some((seed, index))
+        })?;
+        let mut core_seed = <Core as SeedableRng>::Seed::default();
+        core_seed.as_mut()[..8].copy_from_slice(&seed.to_le_bytes());
+        core_seed.as_mut()[8..16].copy_from_slice(&(index as u64).to_le_bytes());
+        Some((Core::from_seed(core_seed), seed, index))
+    }
+}
+
+/// Seed the generator behind [`thread_rng`] deterministically, for
+/// reproducible tests.
+///
+/// Each thread which first uses [`thread_rng`] (or [`random`]) after this
+/// call seeds its generator from `seed` and its thread index instead of from
+/// [`OsRng`], and does not reseed periodically. Threads are numbered from 0
+/// in the order in which they first use the generator after the last call
+/// to this function; hence results are reproducible as long as this order
+/// is. Generators already initialized on other threads (including the
+/// calling thread) are not affected, thus this should be called before
+/// [`thread_rng`] is first used.
+///
+/// The generator is still reseeded from [`OsRng`] in a child process after a
+/// fork. While seeded this way, [`thread_rng_entropy_source`] reports
+/// [`EntropySource::TestSeed`] on the thread.
+///
+/// # Security
+///
+/// This must never be used outside of tests: the resulting generator is
+/// completely predictable, although [`ThreadRng`] still implements
+/// [`CryptoRng`]. Since any code in the process (including any dependency)
+/// may call this function, the `testing` feature must only be enabled for
+/// tests, e.g. via `[dev-dependencies]`, and never in a build which relies on
+/// [`thread_rng`] for security.
+///
+/// # Example
+///
+/// ```
+/// rand::set_global_test_seed(42);
+/// let x: u64 = std::thread::spawn(rand::random).join().unwrap();
+///
+/// rand::set_global_test_seed(42);
+/// let y: u64 = std::thread::spawn(rand::random).join().unwrap();
+/// assert_eq!(x, y);
+///
+/// rand::set_global_test_seed(43);
+/// let z: u64 = std::thread::spawn(rand::random).join().unwrap();
+/// assert_ne!(x, z);
+/// ```
+///
+/// [`random`]: crate::random
+#[cfg(feature = "testing")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "testing")))]
+pub fn set_global_test_seed(seed: u64) {
+    testing::set_seed(seed)
+}
+
 /// Retrieve the lazily-initialized thread-local random number generator,
 /// seeded by the system. Intended to be used in method chaining style,
 /// e.g. `thread_rng().gen::<i32>()`, or cached locally, e.g.
@@ -89,6 +216,52 @@ pub fn thread_rng() -> ThreadRng {
     ThreadRng { rng }
 }
 
+/// The source of entropy used to seed a generator.
+///
+/// Further variants may be added in future versions; matches on this type
+/// should include a wildcard arm.
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum EntropySource {
+    /// The operating system's random number source, via [`OsRng`].
+    Os,
+    /// A deterministic seed set with `set_global_test_seed` (only with the
+    /// `testing` feature), combined with the index of the thread.
+    ///
+    /// The generator is completely predictable.
+    TestSeed {
+        /// The global seed
+        seed: u64,
+        /// The index of the thread among those seeded from `seed`
+        thread_index: usize,
+    },
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// Report the source of entropy used to seed the generator behind
+/// [`thread_rng`] on the current thread.
+///
+/// This reports how the current thread's generator was actually seeded,
+/// initializing the generator first if it was not yet used on this thread.
+/// By default [`ThreadRng`] is seeded from [`OsRng`], reported as
+/// [`EntropySource::Os`]: there is no fallback to weaker sources such as
+/// timer jitter. If the generator was seeded deterministically with
+/// `set_global_test_seed`, [`EntropySource::TestSeed`] is reported. If the
+/// operating system source fails, initialization of the generator panics
+/// (see [`thread_rng`]) and reseeding is postponed (see [`ReseedingRng`]).
+///
+/// Calling this function does not reseed the generator.
+///
+/// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+pub fn thread_rng_entropy_source() -> EntropySource {
+    THREAD_RNG_KEY.with(|_| ());
+    THREAD_RNG_SOURCE.with(|source| source.get())
+        .expect("thread_rng: entropy source not recorded")
+}
+
 impl Default for ThreadRng {
     fn default() -> ThreadRng {
         crate::prelude::thread_rng()
@@ -129,6 +302,173 @@ impl RngCore for ThreadRng {
 
 impl CryptoRng for ThreadRng {}
 
+/// A builder for [`CachedRng`], a configurable variant of [`ThreadRng`].
+///
+/// [`ThreadRng`] uses a fixed algorithm, reseeding threshold and entropy
+/// source. This builder allows specialized deployments to choose each of
+/// these: the PRNG core `R` (any [`BlockRngCore`] + [`SeedableRng`]), the
+/// number of bytes after which to reseed, and the RNG used for seeding and
+/// reseeding. By default the reseeding threshold matches [`ThreadRng`] and
+/// [`OsRng`] is used as entropy source.
+///
+/// The resulting [`CachedRng`] is a cheaply clonable handle which, like
+/// [`ThreadRng`], is not `Send` or `Sync`; it may be installed in a
+/// `thread_local!` to obtain a thread-local generator.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::rngs::{CachedRng, ThreadRngBuilder};
+/// use rand_chacha::ChaCha20Core;
+///
+/// thread_local!(
+///     static MY_RNG: CachedRng<ChaCha20Core> = ThreadRngBuilder::new()
+///         .reseed_threshold(1024 * 1024)
+///         .build()
+///         .unwrap()
+/// );
+///
+/// let x: u32 = MY_RNG.with(|rng| rng.clone().gen());
+/// println!("{}", x);
+/// ```
+///
+/// [`BlockRngCore`]: rand_core::block::BlockRngCore
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+#[derive(Debug)]
+pub struct ThreadRngBuilder<R, Rsdr = OsRng> {
+    threshold: u64,
+    reseeder: Rsdr,
+    core: PhantomData<R>,
+}
+
+impl<R> ThreadRngBuilder<R, OsRng>
+where R: BlockRngCore + SeedableRng
+{
+    /// Create a new builder with the default reseeding threshold and
+    /// [`OsRng`] as entropy source.
+    pub fn new() -> Self {
+        ThreadRngBuilder {
+            threshold: THREAD_RNG_RESEED_THRESHOLD,
+            reseeder: OsRng,
+            core: PhantomData,
+        }
+    }
+}
+
+impl<R> Default for ThreadRngBuilder<R, OsRng>
+where R: BlockRngCore + SeedableRng
+{
+    fn default() -> Self {
+        ThreadRngBuilder::new()
+    }
+}
+
+impl<R, Rsdr> ThreadRngBuilder<R, Rsdr>
+where
+    R: BlockRngCore + SeedableRng,
+    Rsdr: RngCore,
+{
+    /// Set the number of generated bytes after which to reseed the PRNG.
+    ///
+    /// Set it to zero to never reseed based on the number of generated
+    /// values. See [`ReseedingRng::new`].
+    pub fn reseed_threshold(mut self, threshold: u64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set the RNG used to seed and reseed the PRNG.
+    pub fn entropy_source<S: RngCore>(self, source: S) -> ThreadRngBuilder<R, S> {
+        ThreadRngBuilder {
+            threshold: self.threshold,
+            reseeder: source,
+            core: PhantomData,
+        }
+    }
+
+    /// Seed the PRNG from the entropy source and build the generator.
+    pub fn build(mut self) -> Result<CachedRng<R, Rsdr>, Error> {
+        let core = R::from_rng(&mut self.reseeder)?;
+        let rng = ReseedingRng::new(core, self.threshold, self.reseeder);
+        Ok(CachedRng {
+            rng: Rc::new(RefCell::new(rng)),
+        })
+    }
+}
+
+/// A handle to a generator built by [`ThreadRngBuilder`].
+///
+/// Clones of a `CachedRng` share the same generator state. Unlike
+/// [`ThreadRng`], the state is guarded by a `RefCell`, since the
+/// user-supplied entropy source could otherwise access the generator while
+/// it is being reseeded; such re-entrant use panics.
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "std_rng"))))]
+#[derive(Debug)]
+pub struct CachedRng<R, Rsdr = OsRng>
+where
+    R: BlockRngCore + SeedableRng,
+    Rsdr: RngCore,
+{
+    // Rc is explicitly !Send and !Sync
+    rng: Rc<RefCell<ReseedingRng<R, Rsdr>>>,
+}
+
+impl<R, Rsdr> Clone for CachedRng<R, Rsdr>
+where
+    R: BlockRngCore + SeedableRng,
+    Rsdr: RngCore,
+{
+    fn clone(&self) -> Self {
+        CachedRng {
+            rng: self.rng.clone(),
+        }
+    }
+}
+
+impl<R, Rsdr> CachedRng<R, Rsdr>
+where
+    R: BlockRngCore + SeedableRng,
+    Rsdr: RngCore,
+{
+    /// Immediately reseed the generator from its entropy source.
+    pub fn reseed(&self) -> Result<(), Error> {
+        self.rng.borrow_mut().reseed()
+    }
+}
+
+impl<R, Rsdr> RngCore for CachedRng<R, Rsdr>
+where
+    R: BlockRngCore<Item = u32> + SeedableRng,
+    <R as BlockRngCore>::Results: AsRef<[u32]> + AsMut<[u32]>,
+    Rsdr: RngCore,
+{
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.borrow_mut().next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.borrow_mut().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.borrow_mut().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.borrow_mut().try_fill_bytes(dest)
+    }
+}
+
+impl<R, Rsdr> CryptoRng for CachedRng<R, Rsdr>
+where
+    R: BlockRngCore + SeedableRng + CryptoRng,
+    Rsdr: RngCore + CryptoRng,
+{
+}
+
 
 #[cfg(test)]
 mod test {
@@ -139,4 +479,74 @@ mod test {
         r.gen::<i32>();
         assert_eq!(r.gen_range(0..1), 0);
     }
+
+    #[test]
+    fn test_thread_rng_entropy_source() {
+        use super::{thread_rng_entropy_source, EntropySource};
+        use crate::Rng;
+        std::thread::spawn(|| {
+            assert_eq!(thread_rng_entropy_source(), EntropySource::Os);
+            let mut r = crate::thread_rng();
+            r.gen::<u64>();
+            assert_eq!(thread_rng_entropy_source(), EntropySource::Os);
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_thread_rng_builder() {
+        use super::{CachedRng, ThreadRngBuilder};
+        use crate::rngs::mock::StepRng;
+        use crate::{Error, Rng, RngCore};
+        use rand_chacha::ChaCha8Core;
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        // An entropy source counting how often it is used
+        struct Counting(Rc<Cell<usize>>, StepRng);
+        impl RngCore for Counting {
+            fn next_u32(&mut self) -> u32 {
+                self.1.next_u32()
+            }
+            fn next_u64(&mut self) -> u64 {
+                self.1.next_u64()
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.0.set(self.0.get() + 1);
+                self.1.fill_bytes(dest)
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        let count = Rc::new(Cell::new(0));
+        let mut rng: CachedRng<ChaCha8Core, Counting> = ThreadRngBuilder::new()
+            .reseed_threshold(256)
+            .entropy_source(Counting(count.clone(), StepRng::new(1, 1)))
+            .build()
+            .unwrap();
+        assert_eq!(count.get(), 1);
+
+        // ChaCha8Core generates 256 bytes per block, so each block after the
+        // first reseeds
+        let mut buf = [0u8; 256 * 8];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(count.get(), 8);
+        assert!(buf.iter().any(|&b| b != 0));
+
+        // Clones share the same state
+        let mut clone = rng.clone();
+        clone.fill_bytes(&mut buf);
+        assert_eq!(count.get(), 16);
+        rng.reseed().unwrap();
+        assert_eq!(count.get(), 17);
+        assert_eq!(rng.gen_range(0..1), 0);
+
+        // Default configuration
+        let mut rng: CachedRng<ChaCha8Core> = ThreadRngBuilder::new().build().unwrap();
+        rng.gen::<u64>();
+    }
 }
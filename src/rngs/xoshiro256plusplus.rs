@@ -7,7 +7,7 @@
 // except according to those terms.
 
 #[cfg(feature="serde1")] use serde::{Serialize, Deserialize};
-use rand_core::impls::fill_bytes_via_next;
+use rand_core::impls::{fill_bytes_via_next, mix_u64};
 use rand_core::le::read_u64_into;
 use rand_core::{SeedableRng, RngCore, Error};
 
@@ -48,10 +48,7 @@ impl SeedableRng for Xoshiro256PlusPlus {
         let mut seed = Self::Seed::default();
         for chunk in seed.as_mut().chunks_mut(8) {
             state = state.wrapping_add(PHI);
-            let mut z = state;
-            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
-            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
-            z = z ^ (z >> 31);
+            let z = mix_u64(state);
             chunk.copy_from_slice(&z.to_le_bytes());
         }
         Self::from_seed(seed)
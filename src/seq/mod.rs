@@ -656,7 +656,7 @@ impl<'a, S: Index<usize, Output = T> + ?Sized + 'a, T: 'a> ExactSizeIterator
 // possible, primarily in order to produce the same output on 32-bit and 64-bit
 // platforms.
 #[inline]
-fn gen_index<R: Rng + ?Sized>(rng: &mut R, ubound: usize) -> usize {
+pub(crate) fn gen_index<R: Rng + ?Sized>(rng: &mut R, ubound: usize) -> usize {
     if ubound <= (core::u32::MAX as usize) {
         rng.gen_range(0..ubound as u32) as usize
     } else {
@@ -1020,6 +1020,27 @@ mod test {
         assert!(res.1[0] == 1 || res.1[1] == 2 || res.1[2] == 3);
     }
 
+    #[test]
+    fn test_partial_shuffle_varies() {
+        let mut r = crate::test::rng(417);
+        let mut first = [0u32; 5];
+        let mut differs = false;
+        for rep in 0..10 {
+            let mut v = [0u32; 20];
+            for (i, x) in v.iter_mut().enumerate() {
+                *x = i as u32;
+            }
+            let (tail, head) = v.partial_shuffle(&mut r, 5);
+            assert_eq!((tail.len(), head.len()), (5, 15));
+            if rep == 0 {
+                first.copy_from_slice(tail);
+            } else if tail != first {
+                differs = true;
+            }
+        }
+        assert!(differs);
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     fn test_sample_iter() {
@@ -1041,6 +1062,24 @@ mod test {
             .all(|e| { **e >= min_val && **e <= max_val }));
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_slice_choose_multiple_distinct() {
+        let mut r = crate::test::rng(415);
+        let vals = [7u8; 20];
+        let distinct = |refs: &[&u8]| {
+            let mut ptrs: Vec<*const u8> = refs.iter().map(|&x| x as *const u8).collect();
+            ptrs.sort_unstable();
+            ptrs.dedup();
+            ptrs.len()
+        };
+        for &amount in [0, 1, 5, 19, 20, 25].iter() {
+            let chosen: Vec<&u8> = vals.choose_multiple(&mut r, amount).collect();
+            assert_eq!(chosen.len(), amount.min(vals.len()));
+            assert_eq!(distinct(&chosen), chosen.len());
+        }
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     #[cfg_attr(miri, ignore)] // Miri is too slow
@@ -1113,6 +1152,30 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_weighted_float() {
+        let mut r = crate::test::rng(416);
+        let items = [(0.0, 'a'), (0.5, 'b'), (0.0, 'c'), (1.5, 'd')];
+        let mut counts = [0u32; 4];
+        for _ in 0..1000 {
+            let item = items.choose_weighted(&mut r, |item| item.0).unwrap();
+            counts[(item.1 as u8 - b'a') as usize] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        assert_eq!(counts[2], 0);
+        assert!((150..350).contains(&counts[1]));
+
+        assert_eq!(
+            [0.0, 0.0].choose_weighted(&mut r, |x| *x),
+            Err(WeightedError::AllWeightsZero)
+        );
+        assert_eq!(
+            [1.0, ::core::f64::NAN].choose_weighted(&mut r, |x| *x),
+            Err(WeightedError::InvalidWeight)
+        );
+    }
+
     #[test]
     fn value_stability_choose() {
         fn choose<I: Iterator<Item = u32>>(iter: I) -> Option<u32> {
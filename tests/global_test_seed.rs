@@ -0,0 +1,67 @@
+// Copyright 2021 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests of `set_global_test_seed`. These run in their own process, since
+//! the seed affects all threads.
+
+#![cfg(feature = "testing")]
+
+use rand::rngs::EntropySource;
+use rand::RngCore;
+use std::thread;
+
+/// Use `thread_rng` on a new thread, returning its entropy source and its
+/// output before and after the reseeding threshold of `ThreadRng` (64 kiB)
+fn run_thread() -> (EntropySource, u64, u64) {
+    thread::spawn(|| {
+        let mut rng = rand::thread_rng();
+        let first = rng.next_u64();
+        let mut buf = vec![0u8; 1 << 20];
+        rng.fill_bytes(&mut buf);
+        let last = rng.next_u64();
+        (rand::thread_rng_entropy_source(), first, last)
+    })
+    .join()
+    .unwrap()
+}
+
+#[test]
+fn test_global_test_seed() {
+    rand::set_global_test_seed(42);
+    let a0 = run_thread();
+    let a1 = run_thread();
+    assert_eq!(a0.0, EntropySource::TestSeed { seed: 42, thread_index: 0 });
+    assert_eq!(a1.0, EntropySource::TestSeed { seed: 42, thread_index: 1 });
+    assert_ne!(a0.1, a1.1);
+
+    // Setting the seed again restarts the numbering of threads. Output past
+    // the reseeding threshold is reproducible too, since the generator is
+    // never reseeded from the OS.
+    rand::set_global_test_seed(42);
+    assert_eq!(run_thread(), a0);
+    assert_eq!(run_thread(), a1);
+
+    rand::set_global_test_seed(43);
+    let b0 = run_thread();
+    assert_eq!(b0.0, EntropySource::TestSeed { seed: 43, thread_index: 0 });
+    assert_ne!(b0.1, a0.1);
+
+    // A generator which is already initialized is not affected
+    thread::spawn(|| {
+        let mut rng = rand::thread_rng();
+        rng.next_u64();
+        rand::set_global_test_seed(44);
+        assert_eq!(
+            rand::thread_rng_entropy_source(),
+            EntropySource::TestSeed { seed: 43, thread_index: 1 }
+        );
+    })
+    .join()
+    .unwrap();
+    assert_eq!(run_thread().0, EntropySource::TestSeed { seed: 44, thread_index: 0 });
+}